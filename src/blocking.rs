@@ -0,0 +1,88 @@
+//! A blocking HTTP client for the API built on ureq, enabled by the `ureq` feature. It needs no
+//! async runtime, otherwise it behaves as the client of the `reqwest` feature.
+//!
+//! ```no_run
+//! # fn run() -> telegra_ph::Ret<()> {
+//! use telegra_ph::{blocking::Telegraph, CreateAccount};
+//!
+//! let telegraph = Telegraph::new();
+//! let account = CreateAccount::new("Sandbox".into()).send_blocking(&telegraph)?;
+//! # Ok(())
+//! # }
+//! ```
+use std::time::Duration;
+use crate::error::TelegraphError;
+use crate::methods::Ret;
+use crate::request::{HttpMethod, Request, API_URL, FORM_CONTENT_TYPE};
+use crate::transport::BlockingTransport;
+
+/// User agent sent by default.
+pub const USER_AGENT: &str = concat!("telegra-ph/", env!("CARGO_PKG_VERSION"));
+
+/// Time allowed by default for a whole request, from connecting to reading the body.
+pub const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Sends requests of any method to the API, or to whatever server `base_url` points at.
+#[derive(Clone, Debug)]
+pub struct Telegraph {
+    base_url: String,
+    lenient: bool,
+    agent: ureq::Agent,
+}
+
+impl Telegraph {
+    /// A client of <https://api.telegra.ph> with the default user agent and timeout.
+    pub fn new() -> Self {
+        Self::with_raw(API_URL.into(), USER_AGENT.into(), TIMEOUT)
+    }
+
+    pub fn with_raw(base_url: String, user_agent: String, timeout: Duration) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(&user_agent)
+            .timeout(timeout)
+            .build();
+        Self {
+            base_url,
+            lenient: false,
+            agent,
+        }
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Decode responses leniently, keeping values out of the documented limits, see
+    /// `entity::lenient`. Clones share their connection pool, so a lenient clone is cheap to
+    /// make for a single call.
+    pub fn lenient(self, lenient: bool) -> Self {
+        Self { lenient, ..self }
+    }
+}
+
+impl Default for Telegraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BlockingTransport for Telegraph {
+    fn call(&self, request: Request) -> Ret<String> {
+        let url = request.url(&self.base_url);
+        let response = match request.http_method {
+            HttpMethod::Get => self.agent.get(&url).call(),
+            HttpMethod::Post => self.agent.post(&url)
+                .set("Content-Type", FORM_CONTENT_TYPE)
+                .send_string(&request.body().unwrap_or_default()),
+        };
+        let response = response.map_err(|e| match e {
+            ureq::Error::Status(status, _) => TelegraphError::Http(status),
+            e => TelegraphError::Transport(Box::new(e)),
+        })?;
+        response.into_string().map_err(|e| TelegraphError::Transport(Box::new(e)))
+    }
+
+    fn is_lenient(&self) -> bool {
+        self.lenient
+    }
+}
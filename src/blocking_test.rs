@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod test_telegraph {
+    use crate::blocking::*;
+    use crate::methods::*;
+    use crate::entity::*;
+    use crate::error::*;
+    use crate::request::*;
+    use crate::test_server::serve;
+    use std::time::Duration;
+
+    fn client(base_url: &str) -> Telegraph {
+        Telegraph::with_raw(base_url.into(), "stand-in-test".into(), Duration::from_secs(5))
+    }
+
+    #[test]
+    fn test_get() {
+        let server = serve(vec![(200, r#"{"ok":true,"result":{"total_count":1,"pages":[{"path":"Sample-Page-12-15","url":"https://telegra.ph/Sample-Page-12-15","title":"Sample Page","description":"","views":3}]}}"#)], Duration::ZERO);
+        let mut list = GetPageList::new("1234567".into(), 0);
+        list.limit = Limit::with_val(3);
+        let res = list.send_blocking(&client(&server.base_url));
+        let res = res.unwrap();
+        assert_eq!(res.total_count, 1);
+        assert_eq!(res.pages[0].path, "Sample-Page-12-15");
+        let received = server.received.lock().unwrap();
+        assert_eq!(received[0].request_line, "GET /getPageList?access_token=1234567&limit=3 HTTP/1.1");
+        assert_eq!(received[0].header("user-agent"), Some("stand-in-test"));
+    }
+
+    #[test]
+    fn test_post() {
+        let server = serve(vec![(200, r#"{"ok":true,"result":{"path":"Long-Read-10-18","url":"https://telegra.ph/Long-Read-10-18","title":"Long Read","description":"","views":0}}"#)], Duration::ZERO);
+        let text = "Lorem ipsum dolor sit amet. ".repeat(200);
+        let page = EditPage::new("1234567".into(), "Long-Read-10-18".into(), "Long Read".into(), Content::new(vec![Node::String(text)]), false);
+        let res = page.send_blocking(&client(&server.base_url));
+        assert_eq!(res.unwrap().path, "Long-Read-10-18");
+        let received = server.received.lock().unwrap();
+        assert_eq!(received[0].request_line, "POST /editPage/Long-Read-10-18 HTTP/1.1");
+        assert_eq!(received[0].header("content-type"), Some(FORM_CONTENT_TYPE));
+        assert_eq!(Some(received[0].body.clone()), page.to_request().body());
+    }
+
+    #[test]
+    fn test_errors() {
+        let server = serve(vec![(200, r#"{"ok":false,"error":"FLOOD_WAIT_3"}"#)], Duration::ZERO);
+        let res = CreateAccount::new("Sandbox".into()).send_blocking(&client(&server.base_url));
+        let err = res.err().unwrap();
+        assert!(matches!(err, TelegraphError::Api(ApiError::FloodWait(3))));
+        assert_eq!(err.retry_after(), Some(Duration::from_secs(3)));
+
+        let server = serve(vec![(503, "Service Unavailable")], Duration::ZERO);
+        let res = GetAccountInfo::new("1234567".into()).send_blocking(&client(&server.base_url));
+        let err = res.err().unwrap();
+        assert!(matches!(err, TelegraphError::Http(503)));
+        assert!(err.is_retryable());
+
+        let server = serve(vec![(404, "Not Found")], Duration::ZERO);
+        let res = GetAccountInfo::new("1234567".into()).send_blocking(&client(&server.base_url));
+        let err = res.err().unwrap();
+        assert!(matches!(err, TelegraphError::Http(404)));
+        assert!(!err.is_retryable());
+
+        let server = serve(vec![(200, r#"{"ok":true,"result":{"views":40}}"#)], Duration::from_secs(2));
+        let telegraph = Telegraph::with_raw(server.base_url.clone(), USER_AGENT.into(), Duration::from_millis(200));
+        let res = GetViews::new("Sample-Page-12-15".into(), 2016).send_blocking(&telegraph);
+        let err = res.err().unwrap();
+        assert!(matches!(err, TelegraphError::Transport(_)));
+        assert!(err.is_retryable());
+    }
+
+    #[test]
+    fn test_lenient() {
+        let body = r#"{"ok":true,"result":{"short_name":"","page_count":3}}"#;
+        let server = serve(vec![(200, body), (200, body)], Duration::ZERO);
+        let telegraph = client(&server.base_url);
+        let info = GetAccountInfo::new("1234567".into());
+        let res = info.send_blocking(&telegraph);
+        assert!(matches!(res, Err(TelegraphError::Decode(_))));
+        let res = info.send_blocking(&telegraph.lenient(true));
+        assert_eq!(res.unwrap().page_count, Some(3));
+    }
+
+    #[test]
+    fn test_default() {
+        assert_eq!(Telegraph::default().base_url(), API_URL);
+    }
+}
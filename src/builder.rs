@@ -0,0 +1,213 @@
+//! Fluent construction of page content.
+//!
+//! ```
+//! use telegra_ph::builder::{ContentBuilder, Inline};
+//!
+//! let content = ContentBuilder::new()
+//!     .h3("Intro")
+//!     .p(Inline::new().text("Hello ").b("world").text(", see ").a("https://telegra.ph/", "this"))
+//!     .ul(["one", "two"])
+//!     .figure("/file/sample.jpg", "A caption")
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(content.0.len(), 4);
+//! ```
+use crate::entity::{Attrs, Content, Node, Tag};
+use crate::error::ValidationError;
+
+/// Anything that can stand as the children of an element: text, nodes or inline spans.
+pub trait IntoNodes {
+    fn into_nodes(self) -> Vec<Node>;
+}
+
+impl IntoNodes for &str {
+    fn into_nodes(self) -> Vec<Node> {
+        vec![Node::text(self)]
+    }
+}
+
+impl IntoNodes for String {
+    fn into_nodes(self) -> Vec<Node> {
+        vec![Node::String(self)]
+    }
+}
+
+impl IntoNodes for Node {
+    fn into_nodes(self) -> Vec<Node> {
+        vec![self]
+    }
+}
+
+impl IntoNodes for Vec<Node> {
+    fn into_nodes(self) -> Vec<Node> {
+        self
+    }
+}
+
+impl IntoNodes for Inline {
+    fn into_nodes(self) -> Vec<Node> {
+        self.nodes
+    }
+}
+
+/// A run of text and inline elements, e.g. the content of a paragraph.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Inline {
+    nodes: Vec<Node>,
+}
+
+impl Inline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.nodes.push(Node::text(text));
+        self
+    }
+
+    pub fn b(self, children: impl IntoNodes) -> Self {
+        self.span(Tag::B, children)
+    }
+
+    pub fn i(self, children: impl IntoNodes) -> Self {
+        self.span(Tag::I, children)
+    }
+
+    pub fn u(self, children: impl IntoNodes) -> Self {
+        self.span(Tag::U, children)
+    }
+
+    pub fn s(self, children: impl IntoNodes) -> Self {
+        self.span(Tag::S, children)
+    }
+
+    pub fn em(self, children: impl IntoNodes) -> Self {
+        self.span(Tag::Em, children)
+    }
+
+    pub fn strong(self, children: impl IntoNodes) -> Self {
+        self.span(Tag::Strong, children)
+    }
+
+    pub fn code(self, children: impl IntoNodes) -> Self {
+        self.span(Tag::Code, children)
+    }
+
+    /// A link to `href`.
+    pub fn a(mut self, href: impl Into<String>, children: impl IntoNodes) -> Self {
+        self.nodes.push(Node::element(Tag::A, Some(Attrs::href(href)), children.into_nodes()));
+        self
+    }
+
+    pub fn br(mut self) -> Self {
+        self.nodes.push(Node::element(Tag::Br, None, vec![]));
+        self
+    }
+
+    /// Any other node, e.g. an inline image.
+    pub fn node(mut self, node: Node) -> Self {
+        self.nodes.push(node);
+        self
+    }
+
+    fn span(mut self, tag: Tag, children: impl IntoNodes) -> Self {
+        self.nodes.push(Node::element(tag, None, children.into_nodes()));
+        self
+    }
+}
+
+/// Builds the blocks of a page one after the other, see the module documentation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContentBuilder {
+    nodes: Vec<Node>,
+}
+
+impl ContentBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn h3(self, children: impl IntoNodes) -> Self {
+        self.block(Tag::H3, children)
+    }
+
+    pub fn h4(self, children: impl IntoNodes) -> Self {
+        self.block(Tag::H4, children)
+    }
+
+    pub fn p(self, children: impl IntoNodes) -> Self {
+        self.block(Tag::P, children)
+    }
+
+    pub fn blockquote(self, children: impl IntoNodes) -> Self {
+        self.block(Tag::Blockquote, children)
+    }
+
+    pub fn aside(self, children: impl IntoNodes) -> Self {
+        self.block(Tag::Aside, children)
+    }
+
+    pub fn pre(self, children: impl IntoNodes) -> Self {
+        self.block(Tag::Pre, children)
+    }
+
+    /// An unordered list with one `li` per item.
+    pub fn ul<T: IntoNodes>(self, items: impl IntoIterator<Item = T>) -> Self {
+        self.list(Tag::Ul, items)
+    }
+
+    /// An ordered list with one `li` per item.
+    pub fn ol<T: IntoNodes>(self, items: impl IntoIterator<Item = T>) -> Self {
+        self.list(Tag::Ol, items)
+    }
+
+    pub fn hr(mut self) -> Self {
+        self.nodes.push(Node::element(Tag::Hr, None, vec![]));
+        self
+    }
+
+    /// An image at `src` with a caption below it, left out when empty.
+    pub fn figure(self, src: impl Into<String>, caption: impl IntoNodes) -> Self {
+        self.media(Tag::Img, src, caption)
+    }
+
+    /// A video at `src` with a caption below it, left out when empty.
+    pub fn video(self, src: impl Into<String>, caption: impl IntoNodes) -> Self {
+        self.media(Tag::Video, src, caption)
+    }
+
+    /// Any other node, as is.
+    pub fn node(mut self, node: Node) -> Self {
+        self.nodes.push(node);
+        self
+    }
+
+    /// The content built so far, after the checks of `Content::try_new` and `Content::check`.
+    pub fn build(self) -> Result<Content, ValidationError> {
+        let content = Content::try_new(self.nodes)?;
+        content.check()?;
+        Ok(content)
+    }
+
+    fn block(mut self, tag: Tag, children: impl IntoNodes) -> Self {
+        self.nodes.push(Node::element(tag, None, children.into_nodes()));
+        self
+    }
+
+    fn list<T: IntoNodes>(mut self, tag: Tag, items: impl IntoIterator<Item = T>) -> Self {
+        let items = items.into_iter().map(|item| Node::element(Tag::Li, None, item.into_nodes())).collect();
+        self.nodes.push(Node::element(tag, None, items));
+        self
+    }
+
+    fn media(mut self, tag: Tag, src: impl Into<String>, caption: impl IntoNodes) -> Self {
+        let mut children = vec![Node::element(tag, Some(Attrs::src(src)), vec![])];
+        let caption = caption.into_nodes();
+        if !caption.iter().all(|node| matches!(node, Node::String(s) if s.is_empty())) {
+            children.push(Node::element(Tag::Figcaption, None, caption));
+        }
+        self.nodes.push(Node::element(Tag::Figure, None, children));
+        self
+    }
+}
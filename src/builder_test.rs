@@ -0,0 +1,33 @@
+#[cfg(test)]
+mod test_builder {
+    use crate::builder::*;
+    use crate::entity::*;
+
+    #[test]
+    fn test_normal() {
+        let content = ContentBuilder::new()
+            .h3("Intro")
+            .p(Inline::new().text("Hello ").b("world").text(" ").a("https://telegra.ph/", Inline::new().i("link")))
+            .blockquote("Quote")
+            .ul(["one", "two"])
+            .ol(vec![Inline::new().code("x"), Inline::new().s("y")])
+            .pre("fn main() {}")
+            .hr()
+            .figure("/file/1.jpg", "Caption")
+            .video("/file/2.mp4", "")
+            .aside(Inline::new().u("note").br().strong("!"))
+            .build()
+            .unwrap();
+        let expected = r#"[{"tag":"h3","children":["Intro"]},{"tag":"p","children":["Hello ",{"tag":"b","children":["world"]}," ",{"tag":"a","attrs":{"href":"https://telegra.ph/"},"children":[{"tag":"i","children":["link"]}]}]},{"tag":"blockquote","children":["Quote"]},{"tag":"ul","children":[{"tag":"li","children":["one"]},{"tag":"li","children":["two"]}]},{"tag":"ol","children":[{"tag":"li","children":[{"tag":"code","children":["x"]}]},{"tag":"li","children":[{"tag":"s","children":["y"]}]}]},{"tag":"pre","children":["fn main() {}"]},{"tag":"hr"},{"tag":"figure","children":[{"tag":"img","attrs":{"src":"/file/1.jpg"}},{"tag":"figcaption","children":["Caption"]}]},{"tag":"figure","children":[{"tag":"video","attrs":{"src":"/file/2.mp4"}}]},{"tag":"aside","children":[{"tag":"u","children":["note"]},{"tag":"br"},{"tag":"strong","children":["!"]}]}]"#;
+        assert_eq!(serde_json::to_string(&content).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_invalid() {
+        let e = ContentBuilder::new().build().err().unwrap();
+        assert_eq!(e.field, "content");
+        let li = Node::element(Tag::Li, None, vec![Node::text("item")]);
+        let e = ContentBuilder::new().p(vec![li]).build().err().unwrap();
+        assert_eq!(e.actual, "node 0.0: li outside of ol or ul; node 0.0: li inside p");
+    }
+}
@@ -0,0 +1,101 @@
+//! An HTTP client for the API built on reqwest, enabled by the `reqwest` feature.
+//!
+//! ```no_run
+//! # async fn run() -> telegra_ph::Ret<()> {
+//! use telegra_ph::{client::Telegraph, CreateAccount};
+//!
+//! let telegraph = Telegraph::new();
+//! let account = CreateAccount::new("Sandbox".into()).send(&telegraph).await?;
+//! # Ok(())
+//! # }
+//! ```
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+use reqwest::header::CONTENT_TYPE;
+use crate::error::TelegraphError;
+use crate::methods::Ret;
+use crate::request::{HttpMethod, Request, API_URL, FORM_CONTENT_TYPE};
+use crate::transport::Transport;
+
+/// User agent sent by default.
+pub const USER_AGENT: &str = concat!("telegra-ph/", env!("CARGO_PKG_VERSION"));
+
+/// Time allowed by default for a whole request, from connecting to reading the body.
+pub const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Sends requests of any method to the API, or to whatever server `base_url` points at.
+#[derive(Clone, Debug)]
+pub struct Telegraph {
+    base_url: String,
+    lenient: bool,
+    client: reqwest::Client,
+}
+
+impl Telegraph {
+    /// A client of <https://api.telegra.ph> with the default user agent and timeout.
+    ///
+    /// Panics if the TLS backend cannot be initialized, use `with_raw` to handle it.
+    pub fn new() -> Self {
+        Self::with_raw(API_URL.into(), USER_AGENT.into(), TIMEOUT).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn with_raw(base_url: String, user_agent: String, timeout: Duration) -> Ret<Self> {
+        let client = reqwest::Client::builder()
+            .user_agent(user_agent)
+            .timeout(timeout)
+            .build()
+            .map_err(transport_error)?;
+        Ok(Self {
+            base_url,
+            lenient: false,
+            client,
+        })
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Decode responses leniently, keeping values out of the documented limits, see
+    /// `entity::lenient`. Clones share their connection pool, so a lenient clone is cheap to
+    /// make for a single call.
+    pub fn lenient(self, lenient: bool) -> Self {
+        Self { lenient, ..self }
+    }
+
+    async fn execute(&self, request: Request) -> Ret<String> {
+        let url = request.url(&self.base_url);
+        let builder = match request.http_method {
+            HttpMethod::Get => self.client.get(url),
+            HttpMethod::Post => self.client.post(url)
+                .header(CONTENT_TYPE, FORM_CONTENT_TYPE)
+                .body(request.body().unwrap_or_default()),
+        };
+        let response = builder.send().await.map_err(transport_error)?;
+        if !response.status().is_success() {
+            return Err(TelegraphError::Http(response.status().as_u16()));
+        }
+        response.text().await.map_err(transport_error)
+    }
+}
+
+impl Default for Telegraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Transport for Telegraph {
+    fn call<'a>(&'a self, request: Request) -> Pin<Box<dyn Future<Output = Ret<String>> + Send + 'a>> {
+        Box::pin(self.execute(request))
+    }
+
+    fn is_lenient(&self) -> bool {
+        self.lenient
+    }
+}
+
+fn transport_error(e: reqwest::Error) -> TelegraphError {
+    TelegraphError::Transport(Box::new(e))
+}
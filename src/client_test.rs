@@ -0,0 +1,95 @@
+#[cfg(test)]
+mod test_telegraph {
+    use crate::client::*;
+    use crate::methods::*;
+    use crate::entity::*;
+    use crate::error::*;
+    use crate::request::*;
+    use crate::test_server::serve;
+    use std::time::Duration;
+
+    fn client(base_url: &str) -> Telegraph {
+        Telegraph::with_raw(base_url.into(), "stand-in-test".into(), Duration::from_secs(5)).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_get() {
+        let server = serve(vec![(200, r#"{"ok":true,"result":{"views":40}}"#)], Duration::ZERO);
+        let mut views = GetViews::new("Sample-Page-12-15".into(), 2016);
+        views.month = Some(Month::new(12));
+        let res = views.send(&client(&server.base_url)).await;
+        assert_eq!(res.unwrap().views, 40);
+        let received = server.received.lock().unwrap();
+        assert_eq!(received[0].request_line, "GET /getViews/Sample-Page-12-15?year=2016&month=12 HTTP/1.1");
+        assert_eq!(received[0].header("user-agent"), Some("stand-in-test"));
+    }
+
+    #[tokio::test]
+    async fn test_post() {
+        let server = serve(vec![(200, r#"{"ok":true,"result":{"path":"Long-Read-10-18","url":"https://telegra.ph/Long-Read-10-18","title":"Long Read","description":"","views":0}}"#)], Duration::ZERO);
+        let text = "Lorem ipsum dolor sit amet. ".repeat(200);
+        let page = CreatePage::new("1234567".into(), "Long Read".into(), Content::new(vec![Node::String(text)]), false);
+        let res = page.send(&client(&server.base_url)).await;
+        assert_eq!(res.unwrap().path, "Long-Read-10-18");
+        let received = server.received.lock().unwrap();
+        assert_eq!(received[0].request_line, "POST /createPage HTTP/1.1");
+        assert_eq!(received[0].header("content-type"), Some(FORM_CONTENT_TYPE));
+        assert_eq!(Some(received[0].body.clone()), page.to_request().body());
+    }
+
+    #[tokio::test]
+    async fn test_lenient() {
+        let body = r#"{"ok":true,"result":{"short_name":"","page_count":3}}"#;
+        let server = serve(vec![(200, body), (200, body)], Duration::ZERO);
+        let telegraph = client(&server.base_url);
+        let info = GetAccountInfo::new("1234567".into());
+        let res = info.send(&telegraph).await;
+        assert!(matches!(res, Err(TelegraphError::Decode(_))));
+        let res = info.send(&telegraph.lenient(true)).await;
+        assert_eq!(res.unwrap().page_count, Some(3));
+    }
+
+    #[tokio::test]
+    async fn test_api_error() {
+        let server = serve(vec![(200, r#"{"ok":false,"error":"PAGE_NOT_FOUND"}"#)], Duration::ZERO);
+        let res = GetPage::new("Missing-01-01".into(), false).send(&client(&server.base_url)).await;
+        assert!(matches!(res, Err(TelegraphError::Api(ApiError::PageNotFound))));
+    }
+
+    #[tokio::test]
+    async fn test_transport_error() {
+        let server = serve(vec![(502, "Bad Gateway")], Duration::ZERO);
+        let res = RevokeAccessToken::new("1234567".into()).send(&client(&server.base_url)).await;
+        let err = res.err().unwrap();
+        assert!(matches!(err, TelegraphError::Http(502)));
+        assert!(err.is_retryable());
+
+        let server = serve(vec![(400, "Bad Request")], Duration::ZERO);
+        let res = RevokeAccessToken::new("1234567".into()).send(&client(&server.base_url)).await;
+        let err = res.err().unwrap();
+        assert!(matches!(err, TelegraphError::Http(400)));
+        assert!(!err.is_retryable());
+        assert_eq!(err.to_string(), "http status 400");
+
+        let server = serve(vec![(200, r#"{"ok":true,"result":{"views":40}}"#)], Duration::from_secs(2));
+        let telegraph = Telegraph::with_raw(server.base_url.clone(), USER_AGENT.into(), Duration::from_millis(200)).unwrap();
+        let res = GetViews::new("Sample-Page-12-15".into(), 2016).send(&telegraph).await;
+        let err = res.err().unwrap();
+        assert!(matches!(err, TelegraphError::Transport(_)));
+        assert!(err.is_retryable());
+    }
+
+    #[test]
+    fn test_default() {
+        let telegraph = Telegraph::default();
+        assert_eq!(telegraph.base_url(), API_URL);
+    }
+
+    #[test]
+    fn test_send() {
+        fn assert_send<T: Send>(_: &T) {}
+        let telegraph = Telegraph::default();
+        let page = GetPage::new("Sample-Page-12-15".into(), false);
+        assert_send(&page.send(&telegraph));
+    }
+}
@@ -0,0 +1,446 @@
+//! Rendering of content to CommonMark, e.g. to keep pages in a diffable format.
+//!
+//! Headings, paragraphs, emphasis, code, quotes, lists, links, figures with an image and rules
+//! have a Markdown form that `Content::from_markdown` reads back into the same tree. The rest,
+//! `aside`, `u`, and `iframe` or `video` embeds, is written as inline HTML.
+//!
+//! Some trees have no exact Markdown form and read back slightly changed: whitespace at either end
+//! of an emphasis is moved out of it, adjacent emphases or code spans of the same kind are written
+//! as one, line breaks in headings become spaces and those ending a block are dropped. Emphasis
+//! switches to `_` delimiters when `*` ones would merge with their neighbours, and is written as
+//! inline HTML when neither fits.
+use crate::entity::{Content, Node, NodeElement, Tag};
+use crate::traverse::text_of;
+
+impl Content {
+    /// The content as a CommonMark document, see the module documentation.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        write_blocks(&mut out, &self.0);
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// Write `nodes` as blocks separated by blank lines, consecutive inline nodes making up a
+/// paragraph.
+fn write_blocks(out: &mut String, nodes: &[Node]) {
+    let mut blocks: Vec<String> = Vec::new();
+    let mut run: Vec<&Node> = Vec::new();
+    let mut last_list: Option<(&Tag, bool)> = None;
+    for node in nodes {
+        let element = match node {
+            Node::NodeElement(e) if is_block(&e.tag) => e,
+            _ => {
+                run.push(node);
+                continue;
+            }
+        };
+        flush_inline(&mut blocks, &mut run);
+        let mut block = String::new();
+        // Adjacent lists of the same kind merge unless their markers differ.
+        let alternate = matches!(last_list, Some((tag, alternate)) if *tag == element.tag && !alternate);
+        write_block(&mut block, element, alternate);
+        last_list = matches!(element.tag, Tag::Ul | Tag::Ol).then_some((&element.tag, alternate));
+        if !block.is_empty() {
+            blocks.push(block);
+        }
+    }
+    flush_inline(&mut blocks, &mut run);
+    out.push_str(&blocks.join("\n\n"));
+}
+
+fn flush_inline(blocks: &mut Vec<String>, run: &mut Vec<&Node>) {
+    if run.is_empty() {
+        return;
+    }
+    let inline = inline_of(run, false);
+    run.clear();
+    if !inline.trim().is_empty() {
+        blocks.push(inline);
+    }
+}
+
+fn is_block(tag: &Tag) -> bool {
+    tag.is_block() || matches!(tag, Tag::Iframe | Tag::Video)
+}
+
+fn write_block(out: &mut String, element: &NodeElement, alternate: bool) {
+    let children = element.children.as_deref().unwrap_or_default();
+    match element.tag {
+        // A heading ends with its line, so it is written on a single one.
+        Tag::H3 => out.push_str(&format!("### {}", inline(children, true))),
+        Tag::H4 => out.push_str(&format!("#### {}", inline(children, true))),
+        Tag::P | Tag::Li | Tag::Figcaption => out.push_str(&inline(children, false)),
+        Tag::Hr => out.push_str("---"),
+        Tag::Pre => {
+            let mut code = text_of(children);
+            if !code.ends_with('\n') {
+                code.push('\n');
+            }
+            let fence = "`".repeat(longest_run(&code, '`').max(2) + 1);
+            out.push_str(&format!("{}\n{}{}", fence, code, fence));
+        }
+        Tag::Blockquote => {
+            let mut quoted = String::new();
+            write_blocks(&mut quoted, children);
+            out.push_str(&prefix_lines(&quoted, "> ", ">"));
+        }
+        Tag::Ul | Tag::Ol => write_list(out, element, alternate),
+        Tag::Figure => write_figure(out, element),
+        _ => out.push_str(&html(element)),
+    }
+}
+
+fn write_list(out: &mut String, element: &NodeElement, alternate: bool) {
+    let children = element.children.as_deref().unwrap_or_default();
+    let mut items = Vec::new();
+    for (i, child) in children.iter().enumerate() {
+        let marker = match (&element.tag, alternate) {
+            (Tag::Ul, false) => "-".to_string(),
+            (Tag::Ul, true) => "*".to_string(),
+            (_, false) => format!("{}.", i + 1),
+            (_, true) => format!("{})", i + 1),
+        };
+        let body = match child {
+            Node::NodeElement(li) if li.tag == Tag::Li => list_item(li.children.as_deref().unwrap_or_default()),
+            node => list_item(std::slice::from_ref(node)),
+        };
+        let indent = " ".repeat(marker.len() + 1);
+        let body = prefix_lines(&body, &indent, "");
+        items.push(format!("{} {}", marker, body.strip_prefix(&indent).unwrap_or(&body)));
+    }
+    out.push_str(&items.join("\n"));
+}
+
+/// The text of an item followed by its nested lists, each on its own line to keep the list tight.
+fn list_item(nodes: &[Node]) -> String {
+    let mut parts = Vec::new();
+    let mut run: Vec<&Node> = Vec::new();
+    for node in nodes {
+        match node {
+            Node::NodeElement(e) if is_block(&e.tag) => {
+                flush_inline(&mut parts, &mut run);
+                let mut block = String::new();
+                write_block(&mut block, e, false);
+                parts.push(block);
+            }
+            node => run.push(node),
+        }
+    }
+    flush_inline(&mut parts, &mut run);
+    parts.join("\n")
+}
+
+fn write_figure(out: &mut String, element: &NodeElement) {
+    let children = element.children.as_deref().unwrap_or_default();
+    let mut src = None;
+    let mut caption = String::new();
+    for child in children {
+        match child {
+            Node::NodeElement(e) if e.tag == Tag::Img && src.is_none() => src = e.attrs.as_ref().and_then(|a| a.src.as_deref()),
+            Node::NodeElement(e) if e.tag == Tag::Figcaption => caption = text_of(e.children.as_deref().unwrap_or_default()),
+            // Anything else, e.g. a video, has no Markdown form.
+            _ => return out.push_str(&html(element)),
+        }
+    }
+    match src {
+        Some(src) => out.push_str(&format!("![{}]({})", escape(&caption, false), destination(src))),
+        None => out.push_str(&html(element)),
+    }
+}
+
+fn inline(nodes: &[Node], single_line: bool) -> String {
+    let nodes: Vec<&Node> = nodes.iter().collect();
+    inline_of(&nodes, single_line)
+}
+
+/// The inline nodes of a block, without the whitespace at either end the block would drop, nor
+/// the line breaks ending it, which have no Markdown form: a hard break needs a line after it.
+fn inline_of(nodes: &[&Node], single_line: bool) -> String {
+    let mut nodes = nodes;
+    while let [rest @ .., last] = nodes {
+        match last {
+            Node::NodeElement(NodeElement { tag: Tag::Br, .. }) => nodes = rest,
+            Node::String(text) if text.trim().is_empty() => nodes = rest,
+            _ => break,
+        }
+    }
+    let mut inline = Inline::new(true, single_line);
+    inline.nodes(nodes, Class::Space);
+    inline.out.trim_matches(' ').to_string()
+}
+
+/// The kind of a character next to an emphasis delimiter, which decides if the delimiter opens or
+/// closes, see <https://spec.commonmark.org/0.31.2/#left-flanking-delimiter-run>. The start and
+/// the end of a line count as whitespace.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Class {
+    Space,
+    Punct,
+    Other,
+}
+
+impl Class {
+    fn of(c: Option<char>) -> Self {
+        match c {
+            None => Class::Space,
+            Some(c) if c.is_whitespace() => Class::Space,
+            Some(c) if c.is_alphanumeric() => Class::Other,
+            Some(_) => Class::Punct,
+        }
+    }
+
+    /// The class of the first character `node` is written with, `after` if it writes nothing.
+    fn first_of(node: &Node, after: Class) -> Self {
+        let element = match node {
+            Node::String(text) => return text.chars().next().map_or(after, |c| Class::of(Some(c))),
+            Node::NodeElement(e) => e,
+        };
+        let children = element.children.as_deref().unwrap_or_default();
+        match element.tag {
+            Tag::B | Tag::Strong | Tag::I | Tag::Em | Tag::S => match text_of(children).chars().next() {
+                None => after,
+                Some(c) if c.is_whitespace() => Class::Space,
+                Some(_) => Class::Punct,
+            },
+            Tag::Unknown(_) | Tag::P | Tag::H3 | Tag::H4 | Tag::Li | Tag::Figcaption => {
+                children.first().map_or(after, |child| Class::first_of(child, after))
+            }
+            _ => Class::Punct,
+        }
+    }
+}
+
+/// True if `marker` around `body` opens and closes an emphasis, between `before`, what is written
+/// so far, and a character of class `next`.
+fn flanks(marker: &str, before: &str, body: &str, next: Class) -> bool {
+    let c = marker.chars().next().expect("marker is never empty");
+    // An unescaped delimiter character on either side would extend the run.
+    if ends_with_unescaped(before, c) || body.starts_with(c) || ends_with_unescaped(body, c) {
+        return false;
+    }
+    let prev = Class::of(before.chars().last());
+    let (first, last) = (Class::of(body.chars().next()), Class::of(body.chars().last()));
+    let left = |p: Class, n: Class| n != Class::Space && (n != Class::Punct || p != Class::Other);
+    let right = |p: Class, n: Class| p != Class::Space && (p != Class::Punct || n != Class::Other);
+    if c == '_' {
+        let open = left(prev, first) && (!right(prev, first) || prev == Class::Punct);
+        let close = right(last, next) && (!left(last, next) || next == Class::Punct);
+        open && close
+    } else {
+        left(prev, first) && right(last, next)
+    }
+}
+
+/// Writer of inline nodes, keeping track of line starts where block markers must be escaped.
+struct Inline {
+    out: String,
+    line_start: bool,
+    /// Write line breaks as spaces, e.g. in headings.
+    single_line: bool,
+}
+
+impl Inline {
+    fn new(line_start: bool, single_line: bool) -> Self {
+        Self { out: String::new(), line_start, single_line }
+    }
+
+    fn push(&mut self, s: &str) {
+        if !s.is_empty() {
+            self.out.push_str(s);
+            self.line_start = s.ends_with('\n');
+        }
+    }
+
+    /// Write `nodes`, followed by a character of class `after`.
+    fn nodes(&mut self, nodes: &[&Node], after: Class) {
+        let mut i = 0;
+        while i < nodes.len() {
+            let mut end = i + 1;
+            if let Node::NodeElement(e) = nodes[i] {
+                if matches!(e.tag, Tag::B | Tag::Strong | Tag::I | Tag::Em | Tag::S | Tag::Code) {
+                    // Adjacent emphases or code spans of the same kind are one to Markdown.
+                    while matches!(nodes.get(end), Some(Node::NodeElement(n)) if n.tag == e.tag) {
+                        end += 1;
+                    }
+                }
+            }
+            let next = nodes.get(end).map_or(after, |node| Class::first_of(node, after));
+            if end > i + 1 {
+                let elements: Vec<&NodeElement> = nodes[i..end]
+                    .iter()
+                    .filter_map(|node| match node {
+                        Node::NodeElement(e) => Some(e),
+                        Node::String(_) => None,
+                    })
+                    .collect();
+                match elements[0].tag {
+                    Tag::Code => self.code(elements.iter().map(|e| text_of(e.children.as_deref().unwrap_or_default())).collect()),
+                    _ => self.emphasis(&elements, next),
+                }
+            } else {
+                self.node(nodes[i], next);
+            }
+            i = end;
+        }
+    }
+
+    fn children(&mut self, element: &NodeElement, after: Class) {
+        let children: Vec<&Node> = element.children.iter().flatten().collect();
+        self.nodes(&children, after);
+    }
+
+    fn node(&mut self, node: &Node, next: Class) {
+        let element = match node {
+            Node::String(text) => {
+                for (i, line) in text.split('\n').enumerate() {
+                    if i > 0 {
+                        self.push(if self.single_line { " " } else { "\n" });
+                    }
+                    let escaped = escape(line, self.line_start);
+                    self.push(&escaped);
+                }
+                return;
+            }
+            Node::NodeElement(e) => e,
+        };
+        let children = element.children.as_deref().unwrap_or_default();
+        match element.tag {
+            Tag::B | Tag::Strong | Tag::I | Tag::Em | Tag::S => self.emphasis(&[element], next),
+            Tag::Code => self.code(text_of(children)),
+            Tag::A => {
+                let href = element.attrs.as_ref().and_then(|a| a.href.as_deref()).unwrap_or_default();
+                if ends_with_unescaped(&self.out, '!') {
+                    // Followed by the bracket, it would turn the link into an image.
+                    self.out.insert(self.out.len() - 1, '\\');
+                }
+                self.push("[");
+                self.children(element, Class::Punct);
+                self.push(&format!("]({})", destination(href)));
+            }
+            Tag::Img => {
+                let src = element.attrs.as_ref().and_then(|a| a.src.as_deref()).unwrap_or_default();
+                self.push(&format!("![]({})", destination(src)));
+            }
+            Tag::Br if self.single_line => self.push(" "),
+            Tag::Br => self.push("\\\n"),
+            Tag::Unknown(_) | Tag::P | Tag::H3 | Tag::H4 | Tag::Li | Tag::Figcaption => self.children(element, next),
+            _ => self.push(&html(element)),
+        }
+    }
+
+    fn code(&mut self, code: String) {
+        let code = code.replace('\n', " ");
+        let fence = "`".repeat(longest_run(&code, '`') + 1);
+        let pad = code.starts_with('`') || code.ends_with('`') || (code.starts_with(' ') && code.ends_with(' ') && !code.trim().is_empty());
+        let pad = if pad { " " } else { "" };
+        self.push(&format!("{}{}{}{}{}", fence, pad, code, pad, fence));
+    }
+
+    /// Write adjacent `elements` of the same kind of emphasis as one, followed by a character of
+    /// class `next`.
+    fn emphasis(&mut self, elements: &[&NodeElement], next: Class) {
+        let markers: &[&str] = match elements[0].tag {
+            Tag::B | Tag::Strong => &["**", "__"],
+            Tag::I | Tag::Em => &["*", "_"],
+            _ => &["~~"],
+        };
+        let mut inner = Inline::new(false, self.single_line);
+        let children: Vec<&Node> = elements.iter().flat_map(|e| e.children.iter().flatten()).collect();
+        inner.nodes(&children, Class::Punct);
+        // Delimiters next to whitespace do not count, so the whitespace goes out of them.
+        let content = inner.out;
+        let trimmed = content.trim_start_matches([' ', '\n']);
+        let leading = &content[..content.len() - trimmed.len()];
+        let mut body = trimmed.trim_end_matches([' ', '\n']);
+        if body.ends_with('\\') && trimmed[body.len()..].starts_with('\n') {
+            // A hard break stays in, no delimiter fits after it.
+            body = &trimmed[..body.len() + 1];
+        }
+        let trailing = &trimmed[body.len()..];
+        if body.is_empty() {
+            self.push(&content);
+            return;
+        }
+        let before = match leading {
+            "" if self.line_start => "",
+            "" => &self.out,
+            leading => leading,
+        };
+        let next = if trailing.is_empty() { next } else { Class::Space };
+        match markers.iter().find(|marker| flanks(marker, before, body, next)) {
+            Some(marker) => {
+                self.push(leading);
+                self.push(marker);
+                self.push(body);
+                self.push(marker);
+                self.push(trailing);
+            }
+            None => elements.iter().for_each(|element| self.push(&html(element))),
+        }
+    }
+}
+
+/// Backslash escape the characters that could start Markdown syntax, and those that only do at
+/// the start of a line when `line_start` is true.
+fn escape(text: &str, line_start: bool) -> String {
+    let mut out = String::with_capacity(text.len());
+    let digits = text.chars().take_while(char::is_ascii_digit).count();
+    for (i, c) in text.chars().enumerate() {
+        let escaped = match c {
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '~' | '&' | '|' => true,
+            '-' | '+' | '=' => line_start && i == 0,
+            '.' | ')' => line_start && digits > 0 && i == digits,
+            _ => false,
+        };
+        if escaped {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// A link destination, in angle brackets when it contains characters that would end it.
+fn destination(url: &str) -> String {
+    if url.contains([' ', '(', ')', '<', '>']) || url.is_empty() {
+        format!("<{}>", url.replace('<', "%3C").replace('>', "%3E"))
+    } else {
+        url.to_string()
+    }
+}
+
+fn html(element: &NodeElement) -> String {
+    let mut out = String::new();
+    element.write_html(&mut out).expect("writing to a String never fails");
+    out
+}
+
+fn prefix_lines(text: &str, prefix: &str, blank_prefix: &str) -> String {
+    let lines: Vec<String> = text
+        .split('\n')
+        .map(|line| if line.is_empty() { blank_prefix.to_string() } else { format!("{}{}", prefix, line) })
+        .collect();
+    lines.join("\n")
+}
+
+fn ends_with_unescaped(text: &str, c: char) -> bool {
+    let Some(rest) = text.strip_suffix(c) else {
+        return false;
+    };
+    let backslashes = rest.len() - rest.trim_end_matches('\\').len();
+    backslashes % 2 == 0
+}
+
+fn longest_run(text: &str, c: char) -> usize {
+    let mut longest = 0;
+    let mut current = 0;
+    for ch in text.chars() {
+        current = if ch == c { current + 1 } else { 0 };
+        longest = longest.max(current);
+    }
+    longest
+}
@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod test_to_markdown {
+    use crate::entity::*;
+
+    #[test]
+    fn test_normal() {
+        let content = Content::new(crate::content![
+            h3["Intro"],
+            p["Hello, ", strong["world"], " and ", em["you"], ", ", s["old"], " ", code["x`y"], "."],
+            p[a(href = "https://telegra.ph/api")["the docs"], br, "next line"],
+            hr,
+            blockquote["quoted", br, "twice"],
+            pre["fn main() {}\n"],
+            figure[img(src = "/file/1.jpg"), figcaption["A *cat*"]],
+            h4["End"],
+        ]);
+        let md = "### Intro\n\nHello, **world** and *you*, ~~old~~ ``x`y``.\n\n[the docs](https://telegra.ph/api)\\\nnext line\n\n---\n\n> quoted\\\n> twice\n\n```\nfn main() {}\n```\n\n![A \\*cat\\*](/file/1.jpg)\n\n#### End\n";
+        assert_eq!(content.to_markdown(), md);
+    }
+
+    #[test]
+    fn test_lists() {
+        let content = Content::new(crate::content![
+            ul[li["one"], li["two", ol[li["a"], li["b"]]]],
+            ul[li["three"]],
+            ol[li["1. not a list"]],
+        ]);
+        let md = "- one\n- two\n  1. a\n  2. b\n\n* three\n\n1. 1\\. not a list\n";
+        assert_eq!(content.to_markdown(), md);
+    }
+
+    #[test]
+    fn test_escape() {
+        let content = Content::new(crate::content![p["# not a heading, [link] <b> a_b \\ 1 & 2"], p["- item"], p["+1"]]);
+        let md = "\\# not a heading, \\[link\\] \\<b\\> a\\_b \\\\ 1 \\& 2\n\n\\- item\n\n\\+1\n";
+        assert_eq!(content.to_markdown(), md);
+    }
+
+    #[test]
+    fn test_emphasis() {
+        let content = Content::new(crate::content![
+            p[strong["a"], strong["b"], " ", em["a"], em["b"], " ", em["c"], strong["d"], em["e"]],
+            p["a", strong[" b "], "c ", em[" x "], " ", s["y "], "z"],
+            p["a", strong[".b"], " ", strong[em["c"], " d"]],
+        ]);
+        let md = "**ab** *ab* *c*__d__*e*\n\na **b** c  *x*  ~~y~~ z\n\na<strong>.b</strong> __*c* d__\n";
+        assert_eq!(content.to_markdown(), md);
+    }
+
+    #[test]
+    fn test_bang_before_link() {
+        let content = Content::new(crate::content![p["Wow!", a(href = "https://x")["link"], " ![not](/an/image)"]]);
+        assert_eq!(content.to_markdown(), "Wow\\![link](https://x) !\\[not\\](/an/image)\n");
+    }
+
+    #[test]
+    fn test_trailing_break() {
+        let content = Content::new(crate::content![p["a", br], p["b", br, br, " "], blockquote["c", br, "d", br], ul[li["e", br, ol[li["f", br]]]]]);
+        assert_eq!(content.to_markdown(), "a\n\nb\n\n> c\\\n> d\n\n- e\n  1. f\n");
+    }
+
+    #[test]
+    fn test_heading_line_breaks() {
+        let content = Content::new(crate::content![h3["a\nb"], h4["c", br, "d"]]);
+        assert_eq!(content.to_markdown(), "### a b\n\n#### c d\n");
+    }
+
+    #[test]
+    fn test_html_fallback() {
+        let content = Content::new(crate::content![
+            aside["side ", b["note"]],
+            figure[video(src = "/file/1.mp4"), figcaption["clip"]],
+            p["under", u["lined"]],
+            iframe(src = "https://youtube.com/embed/x"),
+        ]);
+        let md = "<aside>side <b>note</b></aside>\n\n<figure><video src=\"/file/1.mp4\"></video><figcaption>clip</figcaption></figure>\n\nunder<u>lined</u>\n\n<iframe src=\"https://youtube.com/embed/x\"></iframe>\n";
+        assert_eq!(content.to_markdown(), md);
+    }
+}
@@ -1,5 +1,38 @@
-use std::collections::HashMap;
-use serde::{Deserialize, de::Deserializer, Serialize};
+use std::cell::Cell;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::io;
+use serde::{Deserialize, de::{self, Deserializer, DeserializeOwned}, Serialize, Serializer};
+use crate::error::{ApiError, ValidationError};
+use crate::limits;
+use crate::methods::Ret;
+
+thread_local! {
+    static LENIENT: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Run `f` with lenient decoding: values the server sends outside of the documented limits, e.g.
+/// an empty `short_name` or an unknown tag, are kept as is instead of failing the whole response.
+/// Only decoding on the current thread is affected, to decode the responses of a transport
+/// leniently use `Transport::is_lenient` instead, e.g. `client::Telegraph::lenient`.
+pub fn lenient<R>(f: impl FnOnce() -> R) -> R {
+    struct Restore(bool);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            LENIENT.with(|lenient| lenient.set(self.0));
+        }
+    }
+    let _restore = Restore(LENIENT.with(|lenient| lenient.replace(true)));
+    f()
+}
+
+/// Turn the outcome of a check made while decoding into a serde error, unless decoding is lenient.
+fn accept<E: de::Error>(check: Result<(), ValidationError>) -> Result<(), E> {
+    match check {
+        Err(e) if !LENIENT.with(Cell::get) => Err(E::custom(e)),
+        _ => Ok(()),
+    }
+}
 
 /// Account name, helps users with several accounts remember which they are currently using.
 /// Displayed to the user above the "Edit/Publish" button on Telegra.ph, other users don't see
@@ -11,55 +44,86 @@ pub struct ShortName(
 );
 impl ShortName {
     pub fn new(short_name: String) -> Self {
-        assert!(!short_name.is_empty(), "short name required");
-        Self (short_name)
+        Self::try_new(short_name).unwrap_or_else(|e| panic!("{}", e))
     }
 
+    pub fn try_new(short_name: String) -> Result<Self, ValidationError> {
+        limits::SHORT_NAME.check(&short_name)?;
+        Ok(Self (short_name))
+    }
+}
+impl TryFrom<String> for ShortName {
+    type Error = ValidationError;
+
+    fn try_from(short_name: String) -> Result<Self, Self::Error> {
+        Self::try_new(short_name)
+    }
 }
 
 fn de_short_name<'de, D>(deserializer: D) -> Result<String, D::Error>
     where
         D: Deserializer<'de> 
 {
-    let s = <&str>::deserialize(deserializer)?;
-    assert!(!s.is_empty(), "short name required and non-empty");
-    Ok(s.into())
+    let s = String::deserialize(deserializer)?;
+    accept(limits::SHORT_NAME.check(&s))?;
+    Ok(s)
 }
 
 /// Default author name used when creating new articles.
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq, Eq)]
 pub struct AuthorName(
     #[serde(deserialize_with="de_author_name")]
     pub String
 );
 impl AuthorName {
     pub fn new(author_name: String) -> Self {
-        assert!(author_name.len() < 128, "author name no more than 128");
-        Self (author_name)
+        Self::try_new(author_name).unwrap_or_else(|e| panic!("{}", e))
     }
 
+    pub fn try_new(author_name: String) -> Result<Self, ValidationError> {
+        limits::AUTHOR_NAME.check(&author_name)?;
+        Ok(Self (author_name))
+    }
+}
+impl TryFrom<String> for AuthorName {
+    type Error = ValidationError;
+
+    fn try_from(author_name: String) -> Result<Self, Self::Error> {
+        Self::try_new(author_name)
+    }
 }
 
 fn de_author_name<'de, D>(deserializer: D) -> Result<String, D::Error>
     where
         D: Deserializer<'de> 
 {
-    let s = <&str>::deserialize(deserializer)?;
-    assert!(s.len() < 128, "author name no more than 128");
-    Ok(s.into())
+    let s = String::deserialize(deserializer)?;
+    accept(limits::AUTHOR_NAME.check(&s))?;
+    Ok(s)
 }
 
 /// Profile link, opened when users click on the author's name below the title. Can be any
 /// link, not necessarily to a Telegram profile or channel.
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq, Eq)]
 pub struct AuthorUrl(
     #[serde(deserialize_with="de_author_url")]
     pub String
 );
 impl AuthorUrl{
     pub fn new(author_url: String) -> Self {
-        assert!(author_url.len() < 512, "author url no more than 512");
-        Self (author_url)
+        Self::try_new(author_url).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_new(author_url: String) -> Result<Self, ValidationError> {
+        limits::AUTHOR_URL.check(&author_url)?;
+        Ok(Self (author_url))
+    }
+}
+impl TryFrom<String> for AuthorUrl {
+    type Error = ValidationError;
+
+    fn try_from(author_url: String) -> Result<Self, Self::Error> {
+        Self::try_new(author_url)
     }
 }
 
@@ -67,40 +131,56 @@ fn de_author_url<'de, D>(deserializer: D) -> Result<String, D::Error>
     where
         D: Deserializer<'de> 
 {
-    let s = <&str>::deserialize(deserializer)?;
-    assert!(s.len() < 512, "author url no more than 512");
-    Ok(s.into())
+    let s = String::deserialize(deserializer)?;
+    accept(limits::AUTHOR_URL.check(&s))?;
+    Ok(s)
 }
 
 const FIELDS: [&str; 5] = ["short_name", "author_name", "author_url", "auth_url", "page_count"];
-#[derive(Deserialize, Serialize)]
+#[derive(Deserialize, Serialize, PartialEq, Eq)]
 pub struct Fields(
     #[serde(deserialize_with="de_fields")]
     pub Vec<String>
 );
 impl Fields {
     pub fn new(fields: Vec<String>) -> Self {
-        for field  in fields.iter() {
-            let field = field as &str;
-            assert!(FIELDS.contains(&field), "Invalid field: {} out of {:?}", field, FIELDS);
-        }
+        Self::try_new(fields).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_new(fields: Vec<String>) -> Result<Self, ValidationError> {
+        check_fields(&fields)?;
         if fields.is_empty() {
-            return Self( vec!["short_name".into(), "author_name".into(), "author_url".into()] );
+            return Ok(Self( vec!["short_name".into(), "author_name".into(), "author_url".into()] ));
         }
-        Self (fields)
+        Ok(Self (fields))
+    }
+}
+impl Default for Fields {
+    fn default() -> Self {
+        Self::new(vec![])
+    }
+}
+impl TryFrom<Vec<String>> for Fields {
+    type Error = ValidationError;
+
+    fn try_from(fields: Vec<String>) -> Result<Self, Self::Error> {
+        Self::try_new(fields)
     }
 }
 
+fn check_fields(fields: &[String]) -> Result<(), ValidationError> {
+    if let Some(field) = fields.iter().find(|field| !FIELDS.contains(&field.as_str())) {
+        return Err(ValidationError::new("fields", format!("one of {:?}", FIELDS), format!("{:?}", field)));
+    }
+    Ok(())
+}
+
 fn de_fields<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
     where
         D: Deserializer<'de> 
 {
-    let s = <Vec<&str>>::deserialize(deserializer)?;
-    s.iter().for_each(|e| {
-        assert!(FIELDS.contains(&e), "Invalid field: {} out of {:?}", e, FIELDS);
-    }
-        );
-    let s = s.into_iter().map(|e| e.to_string()).collect();
+    let s = <Vec<String>>::deserialize(deserializer)?;
+    accept(check_fields(&s))?;
     Ok(s)
 }
 
@@ -112,37 +192,94 @@ pub struct Title(
 );
 impl Title{
     pub fn new(title: String) -> Self {
-        assert!(!title.is_empty(), "title required");
-        assert!(title.len() < 256, "title no more than 256");
-        Self (title)
+        Self::try_new(title).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_new(title: String) -> Result<Self, ValidationError> {
+        limits::TITLE.check(&title)?;
+        Ok(Self (title))
     }
+}
+impl TryFrom<String> for Title {
+    type Error = ValidationError;
 
+    fn try_from(title: String) -> Result<Self, Self::Error> {
+        Self::try_new(title)
+    }
 }
 
 fn de_title<'de, D>(deserializer: D) -> Result<String, D::Error>
     where
         D: Deserializer<'de> 
 {
-    let s = <&str>::deserialize(deserializer)?;
-    assert!(!s.is_empty(), "title required");
-    assert!(s.len() < 256, "title no more than 256");
-    Ok(s.into())
+    let s = String::deserialize(deserializer)?;
+    accept(limits::TITLE.check(&s))?;
+    Ok(s)
 }
 
 /// Content of the page. 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
 pub struct Content(pub Vec<Node>);
 impl Content {
     pub fn new(content: Vec<Node>) -> Self {
-        assert!(!content.is_empty(), "content required");
-        assert!(content.len() < 64 * 1024, "content length is no more than 64*1024");
-        Self (content)
+        Self::try_new(content).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Fails if there is no node, or if the encoded content is over `limits::CONTENT_SIZE`.
+    pub fn try_new(content: Vec<Node>) -> Result<Self, ValidationError> {
+        if content.is_empty() {
+            return Err(ValidationError::new("content", "at least 1 node", "0 nodes"));
+        }
+        let content = Self(content);
+        content.check_size()?;
+        Ok(content)
     }
 
+    /// Exact size in bytes of the JSON sent as the `content` parameter, counted without building
+    /// it.
+    pub fn encoded_size(&self) -> usize {
+        encoded_size(&self.0)
+    }
+
+    /// Fails if `encoded_size` is over `limits::CONTENT_SIZE`.
+    pub fn check_size(&self) -> Result<(), ValidationError> {
+        let size = self.encoded_size();
+        if size > limits::CONTENT_SIZE {
+            return Err(ValidationError::new("content", format!("at most {} bytes", limits::CONTENT_SIZE), format!("{} bytes", size)));
+        }
+        Ok(())
+    }
+}
+
+/// Writer that only counts the bytes written to it.
+struct ByteCount(usize);
+
+impl io::Write for ByteCount {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn encoded_size<T: Serialize + ?Sized>(value: &T) -> usize {
+    let mut count = ByteCount(0);
+    serde_json::to_writer(&mut count, value).expect("content always serializes to JSON");
+    count.0
+}
+impl TryFrom<Vec<Node>> for Content {
+    type Error = ValidationError;
+
+    fn try_from(content: Vec<Node>) -> Result<Self, Self::Error> {
+        Self::try_new(content)
+    }
 }
 
 /// Limits the number of pages to be retrieved.
-#[derive(Deserialize, Serialize)]
+#[derive(Deserialize, Serialize, PartialEq, Eq)]
 pub struct Limit(
     #[serde(deserialize_with="de_limit")]
     pub u8
@@ -153,8 +290,25 @@ impl Limit {
     }
 
     pub fn with_val(limit: u8) -> Self {
-        assert!(limit < 201, "maximal items for each page are no more than 200");
-        Self(limit)
+        Self::try_with_val(limit).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_with_val(limit: u8) -> Result<Self, ValidationError> {
+        limits::LIMIT.check(limit)?;
+        Ok(Self(limit))
+    }
+}
+impl TryFrom<u8> for Limit {
+    type Error = ValidationError;
+
+    fn try_from(limit: u8) -> Result<Self, Self::Error> {
+        Self::try_with_val(limit)
+    }
+}
+
+impl Default for Limit {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -163,7 +317,7 @@ fn de_limit<'de, D>(deserializer: D) -> Result<u8, D::Error>
         D: Deserializer<'de> 
 {
     let s = <u8>::deserialize(deserializer)?;
-    assert!(s < 201, "maximal items for each page are no more than 200");
+    accept(limits::LIMIT.check(s))?;
     Ok(s)
 }
 
@@ -176,9 +330,19 @@ pub struct Year(
 );
 impl Year {
     pub fn new(year: u16) -> Self {
-        assert!(year > 1999, "year range: 2000 - 2100");
-        assert!(year < 2101, "year range: 2000 - 2100" );
-        Self(year)
+        Self::try_new(year).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_new(year: u16) -> Result<Self, ValidationError> {
+        limits::YEAR.check(year)?;
+        Ok(Self(year))
+    }
+}
+impl TryFrom<u16> for Year {
+    type Error = ValidationError;
+
+    fn try_from(year: u16) -> Result<Self, Self::Error> {
+        Self::try_new(year)
     }
 }
 
@@ -187,8 +351,7 @@ fn de_year<'de, D>(deserializer: D) -> Result<u16, D::Error>
         D: Deserializer<'de> 
 {
     let year = <u16>::deserialize(deserializer)?;
-    assert!(year > 1999, "year range: 2000 - 2100");
-    assert!(year < 2101, "year range: 2000 - 2100" );
+    accept(limits::YEAR.check(year))?;
     Ok(year)
 }
 
@@ -202,9 +365,19 @@ pub struct Month(
 );
 impl Month {
     pub fn new(month: u8) -> Self {
-        assert!(month > 0, "month range: 0 - 13");
-        assert!(month < 13, "month range: 0 - 13" );
-        Self(month)
+        Self::try_new(month).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_new(month: u8) -> Result<Self, ValidationError> {
+        limits::MONTH.check(month)?;
+        Ok(Self(month))
+    }
+}
+impl TryFrom<u8> for Month {
+    type Error = ValidationError;
+
+    fn try_from(month: u8) -> Result<Self, Self::Error> {
+        Self::try_new(month)
     }
 }
 
@@ -213,8 +386,7 @@ fn de_month<'de, D>(deserializer: D) -> Result<u8, D::Error>
         D: Deserializer<'de> 
 {
     let month = <u8>::deserialize(deserializer)?;
-    assert!(month > 0, "month range: 0 - 13");
-    assert!(month < 13, "month range: 0 - 13" );
+    accept(limits::MONTH.check(month))?;
     Ok(month)
 }
 
@@ -228,9 +400,19 @@ pub struct Day(
 );
 impl Day {
     pub fn new(day: u8) -> Self {
-        assert!(day > 0, "day range: 1 - 31");
-        assert!(day < 32, "day range: 1 - 31" );
-        Self(day)
+        Self::try_new(day).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_new(day: u8) -> Result<Self, ValidationError> {
+        limits::DAY.check(day)?;
+        Ok(Self(day))
+    }
+}
+impl TryFrom<u8> for Day {
+    type Error = ValidationError;
+
+    fn try_from(day: u8) -> Result<Self, Self::Error> {
+        Self::try_new(day)
     }
 }
 
@@ -239,8 +421,7 @@ fn de_day<'de, D>(deserializer: D) -> Result<u8, D::Error>
         D: Deserializer<'de> 
 {
     let day = <u8>::deserialize(deserializer)?;
-    assert!(day > 0, "day range: 1 - 31");
-    assert!(day < 32, "day range: 1 - 31" );
+    accept(limits::DAY.check(day))?;
     Ok(day)
 }
 
@@ -252,8 +433,19 @@ pub struct Hour(
 );
 impl Hour {
     pub fn new(hour: u8) -> Self {
-        assert!(hour < 25, "hour range: 0 - 24" );
-        Self(hour)
+        Self::try_new(hour).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_new(hour: u8) -> Result<Self, ValidationError> {
+        limits::HOUR.check(hour)?;
+        Ok(Self(hour))
+    }
+}
+impl TryFrom<u8> for Hour {
+    type Error = ValidationError;
+
+    fn try_from(hour: u8) -> Result<Self, Self::Error> {
+        Self::try_new(hour)
     }
 }
 
@@ -262,7 +454,7 @@ fn de_hour<'de, D>(deserializer: D) -> Result<u8, D::Error>
         D: Deserializer<'de> 
 {
     let hour = <u8>::deserialize(deserializer)?;
-    assert!(hour < 25, "hour range: 0 - 24" );
+    accept(limits::HOUR.check(hour))?;
     Ok(hour)
 }
 
@@ -270,61 +462,226 @@ fn de_hour<'de, D>(deserializer: D) -> Result<u8, D::Error>
 const KEYS: [&str; 2] = ["href", "src",];
 /// Optional. Attributes of the DOM element. Key of object represents name of attribute, value
 /// represents value of attribute. Available attributes: href, src.
-#[derive(Deserialize, Serialize)]
-pub struct Attrs(
-    #[serde(deserialize_with="de_attrs")]
-    pub HashMap<String, String>
-);
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Attrs {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub href: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub src: Option<String>,
+    /// Attributes other than href and src, only filled by lenient decoding.
+    #[serde(flatten)]
+    pub unknown: BTreeMap<String, String>,
+}
 impl Attrs {
     pub fn new() -> Self {
-        Self(HashMap::new())
+        Self::default()
+    }
+
+    /// Attributes of a link to `href`.
+    pub fn href(href: impl Into<String>) -> Self {
+        Self { href: Some(href.into()), ..Self::default() }
+    }
+
+    /// Attributes of an embedded resource at `src`.
+    pub fn src(src: impl Into<String>) -> Self {
+        Self { src: Some(src.into()), ..Self::default() }
     }
 
     pub fn insert(&mut self, key: String, val: String) {
-        assert!(KEYS.contains(&(&key as &str)), "Invalid key: {} out of {:?}", key, KEYS);
-        self.0.insert(key, val);
+        self.try_insert(key, val).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_insert(&mut self, key: String, val: String) -> Result<(), ValidationError> {
+        match key.as_str() {
+            "href" => self.href = Some(val),
+            "src" => self.src = Some(val),
+            _ => {
+                check_attr(&key)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        match key {
+            "href" => self.href.as_deref(),
+            "src" => self.src.as_deref(),
+            _ => self.unknown.get(key).map(String::as_str),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.href.iter().count() + self.src.iter().count() + self.unknown.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
     }
 }
 
-fn de_attrs<'de, D>(deserializer: D) -> Result<HashMap<String, String>, D::Error>
-    where
-        D: Deserializer<'de> 
-{
-    let attrs = <HashMap<String, String>>::deserialize(deserializer)?;
-    for key in attrs.keys() {
-        assert!(KEYS.contains(&(&key as &str)), "Invalid key: {} out of {:?}", key, KEYS);
+fn check_attr(key: &str) -> Result<(), ValidationError> {
+    if !KEYS.contains(&key) {
+        return Err(ValidationError::new("attrs", format!("one of {:?}", KEYS), format!("{:?}", key)));
+    }
+    Ok(())
+}
+
+impl<'de> Deserialize<'de> for Attrs {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>
+    {
+        let mut attrs = Attrs::new();
+        for (key, val) in <HashMap<String, String>>::deserialize(deserializer)? {
+            match key.as_str() {
+                "href" => attrs.href = Some(val),
+                "src" => attrs.src = Some(val),
+                _ => {
+                    accept(check_attr(&key))?;
+                    attrs.unknown.insert(key, val);
+                }
+            }
+        }
+        Ok(attrs)
     }
-    Ok(attrs)
 }
 
-const TAGS: [&str; 24] = ["a", "aside", "b", "blockquote", "br", "code", "em", "figcaption", "figure", "h3", "h4", "hr", "i", "iframe", "img", "li", "ol", "p", "pre", "s", "strong", "u", "ul", "video"];
 /// Name of the DOM element. Available tags: a, aside, b, blockquote, br, code, em, figcaption,
 /// figure, h3, h4, hr, i, iframe, img, li, ol, p, pre, s, strong, u, ul, video.
-#[derive(Deserialize, Serialize)]
-pub struct Tag(
-    #[serde(deserialize_with="de_tag")]
-    pub String
-);
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Tag {
+    A,
+    Aside,
+    B,
+    Blockquote,
+    Br,
+    Code,
+    Em,
+    Figcaption,
+    Figure,
+    H3,
+    H4,
+    Hr,
+    I,
+    Iframe,
+    Img,
+    Li,
+    Ol,
+    P,
+    Pre,
+    S,
+    Strong,
+    U,
+    Ul,
+    Video,
+    /// A tag outside of the list, only produced by lenient decoding.
+    Unknown(String),
+}
 impl Tag {
+    /// Every tag the API accepts.
+    pub const ALL: [Tag; 24] = [Tag::A, Tag::Aside, Tag::B, Tag::Blockquote, Tag::Br, Tag::Code, Tag::Em, Tag::Figcaption, Tag::Figure, Tag::H3, Tag::H4, Tag::Hr, Tag::I, Tag::Iframe, Tag::Img, Tag::Li, Tag::Ol, Tag::P, Tag::Pre, Tag::S, Tag::Strong, Tag::U, Tag::Ul, Tag::Video];
+
     pub fn new(tag: String) -> Self {
-        assert!(TAGS.contains(&(&tag as &str)), "Invalid tag: {} out of {:?}", tag, TAGS);
-        Self(tag)
+        Self::try_new(tag).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_new(tag: String) -> Result<Self, ValidationError> {
+        Self::from_name(&tag).ok_or_else(|| tag_error(&tag))
+    }
+
+    /// The known tag named `name`, if any.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|tag| tag.as_str() == name)
+    }
+
+    /// Name of the tag as sent to the API, e.g. `blockquote`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Tag::A => "a",
+            Tag::Aside => "aside",
+            Tag::B => "b",
+            Tag::Blockquote => "blockquote",
+            Tag::Br => "br",
+            Tag::Code => "code",
+            Tag::Em => "em",
+            Tag::Figcaption => "figcaption",
+            Tag::Figure => "figure",
+            Tag::H3 => "h3",
+            Tag::H4 => "h4",
+            Tag::Hr => "hr",
+            Tag::I => "i",
+            Tag::Iframe => "iframe",
+            Tag::Img => "img",
+            Tag::Li => "li",
+            Tag::Ol => "ol",
+            Tag::P => "p",
+            Tag::Pre => "pre",
+            Tag::S => "s",
+            Tag::Strong => "strong",
+            Tag::U => "u",
+            Tag::Ul => "ul",
+            Tag::Video => "video",
+            Tag::Unknown(name) => name,
+        }
+    }
+
+    /// True for tags that start a block of their own rather than flow within text.
+    pub fn is_block(&self) -> bool {
+        matches!(self, Tag::Aside | Tag::Blockquote | Tag::Figcaption | Tag::Figure | Tag::H3 | Tag::H4 | Tag::Hr | Tag::Li | Tag::Ol | Tag::P | Tag::Pre | Tag::Ul)
+    }
+
+    /// True for tags that never have children.
+    pub fn is_void(&self) -> bool {
+        matches!(self, Tag::Br | Tag::Hr | Tag::Img)
     }
 }
+impl TryFrom<String> for Tag {
+    type Error = ValidationError;
 
-fn de_tag<'de, D>(deserializer: D) -> Result<String, D::Error>
-    where
-        D: Deserializer<'de> 
-{
-    let tag = <&str>::deserialize(deserializer)?;
-    assert!(TAGS.contains(&(&tag as &str)), "Invalid tag: {} out of {:?}", tag, TAGS);
-    Ok(tag.into())
+    fn try_from(tag: String) -> Result<Self, Self::Error> {
+        Self::try_new(tag)
+    }
+}
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+fn tag_error(tag: &str) -> ValidationError {
+    let names: Vec<&str> = Tag::ALL.iter().map(Tag::as_str).collect();
+    ValidationError::new("tag", format!("one of {:?}", names), format!("{:?}", tag))
+}
+
+impl Serialize for Tag {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Tag {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>
+    {
+        let tag = String::deserialize(deserializer)?;
+        match Tag::from_name(&tag) {
+            Some(known) => Ok(known),
+            None => {
+                accept(Err(tag_error(&tag)))?;
+                Ok(Tag::Unknown(tag))
+            }
+        }
+    }
 }
 
 /// This object represents a Telegraph account.
 #[derive(Deserialize, Serialize)]
 pub struct Account {
-    pub short_name: ShortName,
+    /// Optional. Only returned when requested through `fields`, which is the case by default.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub short_name: Option<ShortName>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub author_name: Option<AuthorName>,
@@ -349,7 +706,7 @@ pub struct Account {
 impl Account {
     pub fn new(short_name: String) -> Self {
         Self {
-            short_name: ShortName::new(short_name),
+            short_name: Some(ShortName::new(short_name)),
             author_name: None,
             author_url: None,
             access_token: None,
@@ -377,6 +734,12 @@ impl PageList {
     }
 }
 
+impl Default for PageList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// This object represents a page on Telegraph.
 #[derive(Deserialize, Serialize, Debug)]
 pub struct Page {
@@ -441,43 +804,142 @@ impl PageViews {
     }
 }
 
+impl Default for PageViews {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// This abstract object represents a DOM Node. It can be a String which represents a DOM text node
-/// or a NodeElement object.
-#[derive(Deserialize, Serialize, Debug)]
+/// or a NodeElement object, encoded as a bare JSON string or an element object respectively.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(untagged)]
 pub enum Node {
     String(String),
     NodeElement(NodeElement),
 }
+impl<'de> Deserialize<'de> for Node {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>
+    {
+        // Dispatch on the JSON type rather than trying each variant in turn, so that the error of
+        // a malformed element, e.g. an unknown tag, reaches the caller.
+        struct NodeVisitor;
+
+        impl<'de> de::Visitor<'de> for NodeVisitor {
+            type Value = Node;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a text node or an element object")
+            }
+
+            fn visit_str<E: de::Error>(self, text: &str) -> Result<Node, E> {
+                Ok(Node::String(text.into()))
+            }
+
+            fn visit_string<E: de::Error>(self, text: String) -> Result<Node, E> {
+                Ok(Node::String(text))
+            }
+
+            fn visit_map<A: de::MapAccess<'de>>(self, map: A) -> Result<Node, A::Error> {
+                NodeElement::deserialize(de::value::MapAccessDeserializer::new(map)).map(Node::NodeElement)
+            }
+        }
+
+        deserializer.deserialize_any(NodeVisitor)
+    }
+}
+#[allow(clippy::len_without_is_empty)]
 impl Node {
+    /// A text node.
+    pub fn text(text: impl Into<String>) -> Self {
+        Node::String(text.into())
+    }
+
+    /// An element node, without `children` when the list is empty.
+    pub fn element(tag: Tag, attrs: Option<Attrs>, children: Vec<Node>) -> Self {
+        Node::NodeElement(NodeElement {
+            tag,
+            attrs,
+            children: if children.is_empty() { None } else { Some(children) },
+        })
+    }
+
+    /// Size in bytes of the node encoded as JSON, see `Content::encoded_size`.
     pub fn len(&self) -> usize {
-        match self {
-            Node::String(n) => n.len(),
-            Node::NodeElement(n) => n.len()
-        }
+        encoded_size(self)
+    }
+}
+
+/// Append `text` to `out`, merged with the last node if it is text too, as converters that unwrap
+/// elements may leave text nodes apart.
+#[cfg(any(feature = "html", feature = "markdown"))]
+pub(crate) fn push_text(out: &mut Vec<Node>, text: &str) {
+    if text.is_empty() {
+        return;
+    }
+    match out.last_mut() {
+        Some(Node::String(last)) => last.push_str(text),
+        _ => out.push(Node::text(text)),
     }
 }
 
 /// This object represents a DOM element node.
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
 pub struct NodeElement {
     /// Name of the DOM element. Available tags: a, aside, b, blockquote, br, code, em, figcaption,
     /// figure, h3, h4, hr, i, iframe, img, li, ol, p, pre, s, strong, u, ul, video.
-    pub tag: String,
+    pub tag: Tag,
     /// Optional. Attributes of the DOM element. Key of object represents name of attribute, value
     /// represents value of attribute. Available attributes: href, src.
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub attrs: Option<HashMap<String, String>>,
+    pub attrs: Option<Attrs>,
     /// Optional. List of child nodes for the DOM element.
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub children: Option<Vec<Node>>,
 }
+#[allow(clippy::len_without_is_empty)]
 impl NodeElement {
+    /// Size in bytes of the element encoded as JSON, see `Content::encoded_size`.
     pub fn len(&self) -> usize {
-        let attr = format!("{:?}", self.attrs);
-        let child_len = self.children.iter().fold(0, |acc, child| acc + child.len());
-        self.tag.len() + attr.len() + child_len
+        encoded_size(self)
     }
 }
 
+
+/// The JSON object every query responds with. If `ok` equals true, the request was successful,
+/// and the result of the query can be found in the `result` field. Otherwise the error is
+/// explained in the `error` field (e.g. SHORT_NAME_REQUIRED).
+#[derive(Deserialize, Serialize)]
+pub struct ApiResponse<T> {
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<T>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+impl<T: DeserializeOwned> ApiResponse<T> {
+    /// Decode a raw response body into the result it carries.
+    pub fn parse(raw: &str) -> Ret<T> {
+        let response: Self = serde_json::from_str(raw)?;
+        response.into_result()
+    }
+
+    /// Same as `parse`, keeping values out of the documented limits, see `lenient`.
+    pub fn parse_lenient(raw: &str) -> Ret<T> {
+        lenient(|| Self::parse(raw))
+    }
+}
+impl<T> ApiResponse<T> {
+    pub fn into_result(self) -> Ret<T> {
+        match (self.ok, self.result) {
+            (true, Some(result)) => Ok(result),
+            (true, None) => Err(<serde_json::Error as de::Error>::missing_field("result").into()),
+            (false, _) => Err(ApiError::from_code(&self.error.unwrap_or_default()).into()),
+        }
+    }
+}
@@ -89,15 +89,14 @@ mod test_strings {
     }
 
     #[test]
-    #[should_panic]
     fn test_short_name_empty() {
         let s1 = "\"\"";
-        assert_eq!(s1.len(), 2);
-        let r1: Result<ShortName, Error> = serde_json::from_str(s1);
-        assert!(r1.is_ok(), "failed to deserialize");
-        let res1 = r1.unwrap();
-        assert_eq!("", res1.0);
-
+        let d1: Result<ShortName, Error> = serde_json::from_str(s1);
+        assert!(d1.is_err(), "invalid value deserialized");
+        let e = d1.err().unwrap().to_string();
+        assert!(e.starts_with("short_name: expected 1-32 characters, got 0 characters"), "unexpected error: {}", e);
+        let d1: Result<ShortName, Error> = lenient(|| serde_json::from_str(s1));
+        assert!(d1.is_ok(), "invalid value not kept in lenient mode");
     }
 
     #[test]
@@ -122,8 +121,8 @@ mod test_fields {
         let d1: Result<Vec<Tag>, Error> = serde_json::from_str(s1);
         assert!(d1.is_ok(), "failed to deserialized");
         let d1 = d1.unwrap();
-        assert_eq!(d1[0].0, "blockquote", "tag not deserialized");
-        assert_eq!(d1[1].0, "ul", "tag not deserialized");
+        assert_eq!(d1[0], Tag::Blockquote, "tag not deserialized");
+        assert_eq!(d1[1], Tag::Ul, "tag not deserialized");
         let ser1 = serde_json::to_string(&d1);
         assert!(ser1.is_ok(), "failed to serialized");
         let ser1 = ser1.unwrap();
@@ -131,17 +130,15 @@ mod test_fields {
     }
 
     #[test]
-    #[should_panic]
     fn test_over() {
         let s1 = r#""class""#;
         let d1: Result<Tag, Error> = serde_json::from_str(s1);
-        assert!(d1.is_ok(), "failed to deserialized");
-        let d1 = d1.unwrap();
-        assert_eq!(d1.0, "class" , "attribute not deserialized");
-        let ser1 = serde_json::to_string(&d1);
-        assert!(ser1.is_ok(), "failed to serialized");
-        let ser1 = ser1.unwrap();
-        assert_eq!(ser1, s1);
+        assert!(d1.is_err(), "invalid value deserialized");
+        let e = d1.err().unwrap().to_string();
+        assert!(e.starts_with("tag: expected one of"), "unexpected error: {}", e);
+        let d1: Result<Tag, Error> = lenient(|| serde_json::from_str(s1));
+        assert!(d1.is_ok(), "invalid value not kept in lenient mode");
+        assert_eq!(d1.unwrap(), Tag::Unknown("class".into()));
     }
 }
 
@@ -175,14 +172,14 @@ mod test_limit {
     }
 
     #[test]
-    #[should_panic]
     fn test_over_200() {
         let s1 = "203";
         let d1: Result<Limit, Error> = serde_json::from_str(s1);
-        assert!(d1.is_ok(), "failed to deserialized");
-        let d1 = d1.unwrap();
-        assert!(d1.0 < 203, "exceed over 200");
-        assert_eq!(d1.0, 203);
+        assert!(d1.is_err(), "invalid value deserialized");
+        let e = d1.err().unwrap().to_string();
+        assert!(e.starts_with("limit: expected 0-200, got 203"), "unexpected error: {}", e);
+        let d1: Result<Limit, Error> = lenient(|| serde_json::from_str(s1));
+        assert!(d1.is_ok(), "invalid value not kept in lenient mode");
     }
 }
 
@@ -205,29 +202,25 @@ mod test_year {
     }
 
     #[test]
-    #[should_panic]
     fn test_less() {
-        let s2 = "20";
-        let d2: Result<Year, Error> = serde_json::from_str(s2);
-        assert!(d2.is_ok(), "failed to deserialized");
-        let d2 = d2.unwrap();
-        assert!(d2.0 < 2101, "exceed over 200");
-        assert_eq!(d2.0, 20);
-        let ser2 = serde_json::to_string(&d2);
-        assert!(ser2.is_ok(), "failed to serialized");
-        let ser2 = ser2.unwrap();
-        assert_eq!(ser2, "20");
+        let s1 = "20";
+        let d1: Result<Year, Error> = serde_json::from_str(s1);
+        assert!(d1.is_err(), "invalid value deserialized");
+        let e = d1.err().unwrap().to_string();
+        assert!(e.starts_with("year: expected 2000-2100, got 20"), "unexpected error: {}", e);
+        let d1: Result<Year, Error> = lenient(|| serde_json::from_str(s1));
+        assert!(d1.is_ok(), "invalid value not kept in lenient mode");
     }
 
     #[test]
-    #[should_panic]
     fn test_over() {
         let s1 = "2103";
         let d1: Result<Year, Error> = serde_json::from_str(s1);
-        assert!(d1.is_ok(), "failed to deserialized");
-        let d1 = d1.unwrap();
-        assert!(d1.0 < 2101, "exceed over 2100");
-        assert_eq!(d1.0, 2103);
+        assert!(d1.is_err(), "invalid value deserialized");
+        let e = d1.err().unwrap().to_string();
+        assert!(e.starts_with("year: expected 2000-2100, got 2103"), "unexpected error: {}", e);
+        let d1: Result<Year, Error> = lenient(|| serde_json::from_str(s1));
+        assert!(d1.is_ok(), "invalid value not kept in lenient mode");
     }
 }
 
@@ -250,14 +243,14 @@ mod test_month {
     }
 
     #[test]
-    #[should_panic]
     fn test_over() {
         let s1 = "103";
-        let d1: Result<Year, Error> = serde_json::from_str(s1);
-        assert!(d1.is_ok(), "failed to deserialized");
-        let d1 = d1.unwrap();
-        assert!(d1.0 < 13, "exceed over 13");
-        assert_eq!(d1.0, 103);
+        let d1: Result<Month, Error> = serde_json::from_str(s1);
+        assert!(d1.is_err(), "invalid value deserialized");
+        let e = d1.err().unwrap().to_string();
+        assert!(e.starts_with("month: expected 1-12, got 103"), "unexpected error: {}", e);
+        let d1: Result<Month, Error> = lenient(|| serde_json::from_str(s1));
+        assert!(d1.is_ok(), "invalid value not kept in lenient mode");
     }
 }
 
@@ -280,14 +273,14 @@ mod test_day {
     }
 
     #[test]
-    #[should_panic]
     fn test_over() {
         let s1 = "103";
         let d1: Result<Day, Error> = serde_json::from_str(s1);
-        assert!(d1.is_ok(), "failed to deserialized");
-        let d1 = d1.unwrap();
-        assert!(d1.0 < 32, "exceed over 32");
-        assert_eq!(d1.0, 103);
+        assert!(d1.is_err(), "invalid value deserialized");
+        let e = d1.err().unwrap().to_string();
+        assert!(e.starts_with("day: expected 1-31, got 103"), "unexpected error: {}", e);
+        let d1: Result<Day, Error> = lenient(|| serde_json::from_str(s1));
+        assert!(d1.is_ok(), "invalid value not kept in lenient mode");
     }
 }
 
@@ -310,14 +303,14 @@ mod test_hour {
     }
 
     #[test]
-    #[should_panic]
     fn test_over() {
         let s1 = "30";
         let d1: Result<Hour, Error> = serde_json::from_str(s1);
-        assert!(d1.is_ok(), "failed to deserialized");
-        let d1 = d1.unwrap();
-        assert!(d1.0 < 25, "exceed over 25");
-        assert_eq!(d1.0, 30);
+        assert!(d1.is_err(), "invalid value deserialized");
+        let e = d1.err().unwrap().to_string();
+        assert!(e.starts_with("hour: expected 0-24, got 30"), "unexpected error: {}", e);
+        let d1: Result<Hour, Error> = lenient(|| serde_json::from_str(s1));
+        assert!(d1.is_ok(), "invalid value not kept in lenient mode");
     }
 }
 
@@ -334,10 +327,9 @@ mod test_attrs {
         let d1: Result<Attrs, Error> = serde_json::from_str(s1);
         assert!(d1.is_ok(), "failed to deserialized");
         let d1 = d1.unwrap();
-        assert!(d1.0.get("href").is_some() , "attribute not deserialized");
-        assert!(d1.0.get("src").is_some() , "attribute not deserialized");
-        assert_eq!(d1.0.get("href").unwrap(), val1, "attribute value not preserved");
-        assert_eq!(d1.0.get("src").unwrap(), val2, "attribute value not preserved");
+        assert_eq!(d1.href.as_deref(), Some(val1), "attribute value not preserved");
+        assert_eq!(d1.src.as_deref(), Some(val2), "attribute value not preserved");
+        assert!(d1.unknown.is_empty());
         let ser1 = serde_json::to_string(&d1);
         assert!(ser1.is_ok(), "failed to serialized");
         let ser1 = ser1.unwrap();
@@ -345,17 +337,17 @@ mod test_attrs {
     }
 
     #[test]
-    #[should_panic]
     fn test_over() {
         let s1 = r#"{"_href":"https://test.com/?data=test.img"}"#;
         let d1: Result<Attrs, Error> = serde_json::from_str(s1);
-        assert!(d1.is_ok(), "failed to deserialized");
+        assert!(d1.is_err(), "invalid value deserialized");
+        let e = d1.err().unwrap().to_string();
+        assert!(e.starts_with("attrs: expected one of"), "unexpected error: {}", e);
+        let d1: Result<Attrs, Error> = lenient(|| serde_json::from_str(s1));
+        assert!(d1.is_ok(), "invalid value not kept in lenient mode");
         let d1 = d1.unwrap();
-        assert!(d1.0.get("_href").is_some() , "attribute not deserialized");
-        let ser1 = serde_json::to_string(&d1);
-        assert!(ser1.is_ok(), "failed to serialized");
-        let ser1 = ser1.unwrap();
-        assert_eq!(ser1, s1);
+        assert_eq!(d1.get("_href"), Some("https://test.com/?data=test.img"));
+        assert_eq!(serde_json::to_string(&d1).unwrap(), s1);
     }
 }
 
@@ -369,8 +361,8 @@ mod test_tag {
         let d1: Result<Vec<Tag>, Error> = serde_json::from_str(s1);
         assert!(d1.is_ok(), "failed to deserialized");
         let d1 = d1.unwrap();
-        assert_eq!(d1[0].0, "blockquote", "tag not deserialized");
-        assert_eq!(d1[1].0, "ul", "tag not deserialized");
+        assert_eq!(d1[0], Tag::Blockquote, "tag not deserialized");
+        assert_eq!(d1[1], Tag::Ul, "tag not deserialized");
         let ser1 = serde_json::to_string(&d1);
         assert!(ser1.is_ok(), "failed to serialized");
         let ser1 = ser1.unwrap();
@@ -378,17 +370,15 @@ mod test_tag {
     }
 
     #[test]
-    #[should_panic]
     fn test_over() {
         let s1 = r#""class""#;
         let d1: Result<Tag, Error> = serde_json::from_str(s1);
-        assert!(d1.is_ok(), "failed to deserialized");
-        let d1 = d1.unwrap();
-        assert_eq!(d1.0, "class" , "attribute not deserialized");
-        let ser1 = serde_json::to_string(&d1);
-        assert!(ser1.is_ok(), "failed to serialized");
-        let ser1 = ser1.unwrap();
-        assert_eq!(ser1, s1);
+        assert!(d1.is_err(), "invalid value deserialized");
+        let e = d1.err().unwrap().to_string();
+        assert!(e.starts_with("tag: expected one of"), "unexpected error: {}", e);
+        let d1: Result<Tag, Error> = lenient(|| serde_json::from_str(s1));
+        assert!(d1.is_ok(), "invalid value not kept in lenient mode");
+        assert_eq!(d1.unwrap(), Tag::Unknown("class".into()));
     }
 }
 
@@ -405,7 +395,7 @@ mod test_account {
         let d1 = d1.unwrap();
         assert!(d1.author_url.is_none());
         assert!(d1.auth_url.is_none());
-        assert_eq!(d1.short_name.0, "short name");
+        assert_eq!(d1.short_name.as_ref().unwrap().0, "short name");
         assert_eq!(d1.author_name.as_ref().unwrap().0, "author name");
         assert_eq!(d1.access_token.as_ref().unwrap(), "1234567");
         assert_eq!(d1.page_count.as_ref().unwrap(), &3);
@@ -416,12 +406,12 @@ mod test_account {
     }
 
     #[test]
-    #[should_panic]
     fn test_no_short_name() {
         let s1 = r#"{"author_name":"author name","access_token":"1234567","page_count":3}"#;
         let d1: Result<Account, Error> = serde_json::from_str(s1);
         assert!(d1.is_ok(), "failed to deserialized");
         let d1 = d1.unwrap();
+        assert!(d1.short_name.is_none());
         assert!(d1.author_url.is_none());
         let ser = serde_json::to_string(&d1);
         assert!(ser.is_ok());
@@ -458,7 +448,7 @@ mod test_page {
 
     #[test]
     fn test_normal() {
-        let s1 = r#"{"path":"page path","url":"https://test.com/","title":"title","description":"","views":1000,"content":[{"String":"text node"},{"NodeElement":{"tag":"h3","attrs":{"src":"https://test.com/?data=test.img"}}}]}"#;
+        let s1 = r#"{"path":"page path","url":"https://test.com/","title":"title","description":"","views":1000,"content":["text node",{"tag":"h3","attrs":{"src":"https://test.com/?data=test.img"}}]}"#;
         let d1: Result<Page, Error> = serde_json::from_str(s1);
         dbg!(&d1);
         assert!(d1.is_ok(), "failed to deserialized");
@@ -472,8 +462,8 @@ mod test_page {
             assert_eq!(e, "text node");
         }
         if let Node::NodeElement(ref e) = item[1] {
-            assert_eq!(e.tag, "h3");
-            assert_eq!(e.attrs.as_ref().unwrap().get("src").unwrap(), "https://test.com/?data=test.img");
+            assert_eq!(e.tag, Tag::H3);
+            assert_eq!(e.attrs.as_ref().unwrap().src.as_deref(), Some("https://test.com/?data=test.img"));
         }
         let ser = serde_json::to_string(&d1);
         assert!(ser.is_ok());
@@ -508,21 +498,21 @@ mod test_node_element {
 
     #[test]
     fn test_normal() {
-        let s1 = r#"{"tag":"a","attrs":{"href":"https://test.com/"},"children":[{"String":"A text node"},{"NodeElement":{"tag":"figure","attrs":{"src":"https://test.com/?data=test.img"}}}]}"#;
+        let s1 = r#"{"tag":"a","attrs":{"href":"https://test.com/"},"children":["A text node",{"tag":"figure","attrs":{"src":"https://test.com/?data=test.img"}}]}"#;
         let d1: Result<NodeElement, Error> = serde_json::from_str(s1);
         dbg!(&d1);
         assert!(d1.is_ok(), "failed to deserialized");
         let d1 = d1.unwrap();
-        assert_eq!(d1.tag, "a");
-        assert_eq!(d1.attrs.as_ref().unwrap().get("href").unwrap(), "https://test.com/");
+        assert_eq!(d1.tag, Tag::A);
+        assert_eq!(d1.attrs.as_ref().unwrap().href.as_deref(), Some("https://test.com/"));
         let item = &d1.children.as_ref().unwrap();
         if let Node::String(ref e) = item[0] {
             assert_eq!(e, "A text node");
         }
 
         if let Node::NodeElement(ref e) = item[1] {
-            assert_eq!(e.tag, "figure");
-            assert_eq!(e.attrs.as_ref().unwrap().get("src").unwrap(), "https://test.com/?data=test.img");
+            assert_eq!(e.tag, Tag::Figure);
+            assert_eq!(e.attrs.as_ref().unwrap().get("src"), Some("https://test.com/?data=test.img"));
         }
         let ser = serde_json::to_string(&d1);
         assert!(ser.is_ok());
@@ -530,3 +520,231 @@ mod test_node_element {
         assert_eq!(ser, s1, "failed to serialized");
     }
 }
+
+#[cfg(test)]
+mod test_api_response {
+    use crate::entity::*;
+    use crate::error::*;
+
+    #[test]
+    fn test_ok() {
+        let s1 = r#"{"ok":true,"result":{"short_name":"Sandbox","author_name":"Anonymous","author_url":"","access_token":"abc","auth_url":"https://edit.telegra.ph/auth/xyz"}}"#;
+        let d1 = ApiResponse::<Account>::parse(s1);
+        assert!(d1.is_ok(), "failed to decode");
+        let d1 = d1.unwrap();
+        assert_eq!(d1.short_name.as_ref().unwrap().0, "Sandbox");
+        assert_eq!(d1.access_token.as_ref().unwrap(), "abc");
+
+        let s2 = r#"{"ok":true,"result":{"path":"Sample-Page-12-15","url":"https://telegra.ph/Sample-Page-12-15","title":"Sample Page","description":"","views":0,"can_edit":true}}"#;
+        let d2 = ApiResponse::<Page>::parse(s2).unwrap();
+        assert_eq!(d2.path, "Sample-Page-12-15");
+        assert_eq!(d2.can_edit, Some(true));
+
+        let s3 = r#"{"ok":true,"result":{"total_count":0,"pages":[]}}"#;
+        let d3 = ApiResponse::<PageList>::parse(s3).unwrap();
+        assert_eq!(d3.total_count, 0);
+
+        let s4 = r#"{"ok":true,"result":{"views":40}}"#;
+        let d4 = ApiResponse::<PageViews>::parse(s4).unwrap();
+        assert_eq!(d4.views, 40);
+    }
+
+    #[test]
+    fn test_error() {
+        let s1 = r#"{"ok":false,"error":"SHORT_NAME_REQUIRED"}"#;
+        let d1 = ApiResponse::<Account>::parse(s1);
+        assert!(d1.is_err());
+        match d1.err().unwrap() {
+            TelegraphError::Api(e) => {
+                assert_eq!(e, ApiError::ShortNameRequired);
+                assert_eq!(e.to_string(), "SHORT_NAME_REQUIRED");
+            }
+            e => panic!("unexpected error: {}", e),
+        }
+
+        let s2 = r#"{"ok":true}"#;
+        let d2 = ApiResponse::<PageViews>::parse(s2);
+        assert!(matches!(d2, Err(TelegraphError::Decode(_))));
+        let s3 = r#"{"result":{"views":40}}"#;
+        let d3 = ApiResponse::<PageViews>::parse(s3);
+        assert!(matches!(d3, Err(TelegraphError::Decode(_))));
+    }
+}
+
+#[cfg(test)]
+mod test_try_new {
+    use crate::entity::*;
+    use crate::error::*;
+
+    #[test]
+    fn test_strings() {
+        assert!(ShortName::try_new("Sandbox".into()).is_ok());
+        let e = ShortName::try_new("".into()).err().unwrap();
+        assert_eq!(e.field, "short_name");
+        let e = AuthorName::try_from("a".repeat(200)).err().unwrap();
+        assert_eq!(e, ValidationError::new("author_name", "0-128 characters", "200 characters"));
+        let e = AuthorUrl::try_from("a".repeat(600)).err().unwrap();
+        assert_eq!(e.field, "author_url");
+        assert_eq!(e.actual, "600 characters");
+        let e = Title::try_from(String::new()).err().unwrap();
+        assert_eq!(e.to_string(), "title: expected 1-256 characters, got 0 characters");
+        assert!(Title::try_from("title".to_string()).is_ok());
+        assert!(Content::try_new(vec![]).is_err());
+    }
+
+    #[test]
+    fn test_numbers() {
+        assert!(Limit::try_with_val(200).is_ok());
+        assert_eq!(Limit::try_from(201).err().unwrap(), ValidationError::new("limit", "0-200", "201"));
+        assert!(Year::try_new(2016).is_ok());
+        assert_eq!(Year::try_from(1999).err().unwrap().to_string(), "year: expected 2000-2100, got 1999");
+        assert!(Month::try_new(0).is_err());
+        assert!(Month::try_new(12).is_ok());
+        assert!(Day::try_new(32).is_err());
+        assert!(Hour::try_new(24).is_ok());
+        assert!(Hour::try_new(25).is_err());
+    }
+
+    #[test]
+    fn test_names() {
+        let e = Tag::try_from("div".to_string()).err().unwrap();
+        assert_eq!(e.field, "tag");
+        assert_eq!(e.actual, "\"div\"");
+        assert_eq!(Tag::try_new("figure".into()), Ok(Tag::Figure));
+        assert_eq!(Tag::ALL.len(), 24);
+        assert!(Tag::ALL.iter().all(|tag| Tag::from_name(tag.as_str()).as_ref() == Some(tag)));
+        let mut attrs = Attrs::new();
+        assert!(attrs.try_insert("href".into(), "https://telegra.ph/".into()).is_ok());
+        assert!(attrs.try_insert("class".into(), "x".into()).is_err());
+        assert_eq!(attrs.len(), 1);
+        assert_eq!(attrs, Attrs::href("https://telegra.ph/"));
+        assert!(Fields::try_new(vec!["page_count".into()]).is_ok());
+        let e = Fields::try_new(vec!["views".into()]).err().unwrap();
+        assert_eq!(e.field, "fields");
+        let e = TelegraphError::from(e);
+        assert!(matches!(e, TelegraphError::Validation(_)));
+    }
+
+    #[test]
+    #[should_panic(expected = "author_name: expected 0-128 characters, got 129 characters")]
+    fn test_panic() {
+        AuthorName::new("a".repeat(129));
+    }
+}
+
+#[cfg(test)]
+mod test_lenient {
+    use crate::entity::*;
+    use crate::error::*;
+    use serde_json::Error;
+
+    #[test]
+    fn test_account() {
+        let s1 = r#"{"ok":true,"result":{"short_name":"","page_count":3}}"#;
+        let d1 = ApiResponse::<Account>::parse(s1);
+        assert!(matches!(d1, Err(TelegraphError::Decode(_))));
+        let d1 = ApiResponse::<Account>::parse_lenient(s1);
+        assert!(d1.is_ok(), "failed to decode leniently");
+        let d1 = d1.unwrap();
+        assert_eq!(d1.short_name.as_ref().unwrap().0, "");
+        assert_eq!(d1.page_count, Some(3));
+        // leniency does not outlive the call
+        assert!(ApiResponse::<Account>::parse(s1).is_err());
+    }
+
+    #[test]
+    fn test_account_fields() {
+        // getAccountInfo with fields=["page_count"] only returns the page count
+        let s1 = r#"{"ok":true,"result":{"page_count":3}}"#;
+        let d1 = ApiResponse::<Account>::parse(s1);
+        assert!(d1.is_ok(), "failed to decode");
+        let d1 = d1.unwrap();
+        assert!(d1.short_name.is_none());
+        assert_eq!(d1.page_count, Some(3));
+        assert_eq!(serde_json::to_string(&d1).unwrap(), r#"{"page_count":3}"#);
+    }
+
+    #[test]
+    fn test_escaped() {
+        let s1 = r#""Café \"Bar\"""#;
+        let d1: Result<Title, Error> = serde_json::from_str(s1);
+        assert!(d1.is_ok(), "failed to deserialize escaped string");
+        assert_eq!(d1.unwrap().0, "Café \"Bar\"");
+    }
+
+    #[test]
+    fn test_restored_after_panic() {
+        let r = std::panic::catch_unwind(|| lenient(|| panic!("boom")));
+        assert!(r.is_err());
+        let d1: Result<Hour, Error> = serde_json::from_str("30");
+        assert!(d1.is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_node {
+    use crate::entity::*;
+
+    const SAMPLE: &str = r#"[{"tag":"p","children":["Hello, world!"]}]"#;
+
+    #[test]
+    fn test_sample() {
+        let content: Content = serde_json::from_str(SAMPLE).unwrap();
+        let expected = Content::new(vec![Node::NodeElement(NodeElement {
+            tag: Tag::P,
+            attrs: None,
+            children: Some(vec![Node::String("Hello, world!".into())]),
+        })]);
+        assert_eq!(content, expected);
+        assert_eq!(serde_json::to_string(&content).unwrap(), SAMPLE);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let s1 = r#"["Intro ",{"tag":"a","attrs":{"href":"https://telegra.ph/"},"children":["link"]},{"tag":"br"},{"tag":"ul","children":[{"tag":"li","children":["one"]},{"tag":"li","children":[{"tag":"b","children":["two"]}]}]}]"#;
+        let content: Content = serde_json::from_str(s1).unwrap();
+        assert_eq!(content.0.len(), 4);
+        assert!(matches!(content.0[0], Node::String(ref s) if s == "Intro "));
+        assert!(matches!(content.0[2], Node::NodeElement(ref e) if e.tag == Tag::Br && e.children.is_none()));
+        assert_eq!(serde_json::to_string(&content).unwrap(), s1);
+        let again: Content = serde_json::from_str(&serde_json::to_string(&content).unwrap()).unwrap();
+        assert_eq!(again, content);
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(serde_json::from_str::<Node>("42").is_err());
+        assert!(serde_json::from_str::<Node>(r#"{"String":"text"}"#).is_err());
+        assert!(serde_json::from_str::<Node>(r#"{"tag":"div","children":["text"]}"#).is_err());
+        assert!(serde_json::from_str::<Node>(r#"{"tag":"a","attrs":{"class":"x"}}"#).is_err());
+    }
+
+    #[test]
+    fn test_invalid_message() {
+        let e = serde_json::from_str::<Node>("42").unwrap_err().to_string();
+        assert!(e.starts_with("invalid type: integer `42`, expected a text node or an element object"), "unexpected error: {}", e);
+        let e = serde_json::from_str::<Vec<Node>>(r#"["text",{"tag":"p","children":[{"tag":"div"}]}]"#).unwrap_err().to_string();
+        assert!(e.starts_with("tag: expected one of [\"a\", "), "unexpected error: {}", e);
+        assert!(e.contains(r#"got "div""#), "unexpected error: {}", e);
+        let e = serde_json::from_str::<Node>(r#"{"tag":"a","attrs":{"class":"x"}}"#).unwrap_err().to_string();
+        assert!(e.starts_with("attrs: expected "), "unexpected error: {}", e);
+        let e = serde_json::from_str::<Node>(r#"{"children":[]}"#).unwrap_err().to_string();
+        assert!(e.starts_with("missing field `tag`"), "unexpected error: {}", e);
+    }
+
+    #[test]
+    fn test_lenient() {
+        let s1 = r#"[{"tag":"div","attrs":{"href":"/","class":"x"},"children":["text"]}]"#;
+        let content: Content = lenient(|| serde_json::from_str(s1)).unwrap();
+        match &content.0[0] {
+            Node::NodeElement(e) => {
+                assert_eq!(e.tag, Tag::Unknown("div".into()));
+                let attrs = e.attrs.as_ref().unwrap();
+                assert_eq!(attrs.href.as_deref(), Some("/"));
+                assert_eq!(attrs.get("class"), Some("x"));
+            }
+            node => panic!("unexpected node: {:?}", node),
+        }
+        assert_eq!(serde_json::to_string(&content).unwrap(), s1);
+    }
+}
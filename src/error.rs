@@ -0,0 +1,197 @@
+use std::fmt;
+use std::time::Duration;
+
+/// Everything that can go wrong while talking to Telegraph.
+#[derive(Debug)]
+pub enum TelegraphError {
+    /// A parameter breaks one of the limits of the API, nothing was sent.
+    Validation(ValidationError),
+    /// The request could not be delivered or its response not received, e.g. the connection
+    /// failed or timed out.
+    Transport(Box<dyn std::error::Error + Send + Sync>),
+    /// The server answered with an HTTP status other than success.
+    Http(u16),
+    /// The response body is not what the API documents.
+    Decode(serde_json::Error),
+    /// The server answered with `ok` equal false.
+    Api(ApiError),
+}
+
+impl TelegraphError {
+    /// True if sending the same request again later may succeed: connection failures, timeouts,
+    /// server errors and rate limiting, not requests the server refused.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            TelegraphError::Transport(_) => true,
+            TelegraphError::Http(status) => *status >= 500 || *status == 429,
+            TelegraphError::Api(e) => matches!(e, ApiError::FloodWait(_) | ApiError::PageSaveFailed),
+            _ => false,
+        }
+    }
+
+    /// True if the access token has to be renewed before trying again.
+    pub fn is_auth(&self) -> bool {
+        matches!(self, TelegraphError::Api(ApiError::AccessTokenInvalid))
+    }
+
+    /// How long the server asked to wait before the next request, if it did.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            TelegraphError::Api(ApiError::FloodWait(secs)) => Some(Duration::from_secs(*secs)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for TelegraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TelegraphError::Validation(e) => write!(f, "invalid parameter: {}", e),
+            TelegraphError::Transport(e) => write!(f, "transport failed: {}", e),
+            TelegraphError::Http(status) => write!(f, "http status {}", status),
+            TelegraphError::Decode(e) => write!(f, "malformed response: {}", e),
+            TelegraphError::Api(e) => write!(f, "api error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for TelegraphError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TelegraphError::Transport(e) => Some(e.as_ref()),
+            TelegraphError::Http(_) => None,
+            TelegraphError::Decode(e) => Some(e),
+            TelegraphError::Validation(e) => Some(e),
+            TelegraphError::Api(e) => Some(e),
+        }
+    }
+}
+
+impl From<serde_json::Error> for TelegraphError {
+    fn from(e: serde_json::Error) -> Self {
+        TelegraphError::Decode(e)
+    }
+}
+
+impl From<ValidationError> for TelegraphError {
+    fn from(e: ValidationError) -> Self {
+        TelegraphError::Validation(e)
+    }
+}
+
+impl From<ApiError> for TelegraphError {
+    fn from(e: ApiError) -> Self {
+        TelegraphError::Api(e)
+    }
+}
+
+/// A value refused by one of the limits of the API.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// Name of the parameter, as in the API, e.g. `short_name`.
+    pub field: &'static str,
+    /// What the API accepts, e.g. `1-32 characters`.
+    pub limit: String,
+    /// What was given instead.
+    pub actual: String,
+}
+
+impl ValidationError {
+    pub fn new(field: &'static str, limit: impl Into<String>, actual: impl Into<String>) -> Self {
+        Self {
+            field,
+            limit: limit.into(),
+            actual: actual.into(),
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: expected {}, got {}", self.field, self.limit, self.actual)
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Error reported by the server in the `error` field of a response. Codes not listed here are
+/// kept as is in `Unknown`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiError {
+    AccessTokenInvalid,
+    AuthorNameTooLong,
+    AuthorUrlTooLong,
+    ContentFormatInvalid,
+    ContentRequired,
+    ContentTextRequired,
+    ContentTooBig,
+    FieldsFormatInvalid,
+    PageAccessDenied,
+    PageNotFound,
+    PageSaveFailed,
+    ShortNameRequired,
+    ShortNameTooLong,
+    TitleRequired,
+    TitleTooLong,
+    /// Too many requests, retry after the given number of seconds.
+    FloodWait(u64),
+    Unknown(String),
+}
+
+impl ApiError {
+    pub fn from_code(code: &str) -> Self {
+        match code {
+            "ACCESS_TOKEN_INVALID" => ApiError::AccessTokenInvalid,
+            "AUTHOR_NAME_TOO_LONG" => ApiError::AuthorNameTooLong,
+            "AUTHOR_URL_TOO_LONG" => ApiError::AuthorUrlTooLong,
+            "CONTENT_FORMAT_INVALID" => ApiError::ContentFormatInvalid,
+            "CONTENT_REQUIRED" => ApiError::ContentRequired,
+            "CONTENT_TEXT_REQUIRED" => ApiError::ContentTextRequired,
+            "CONTENT_TOO_BIG" => ApiError::ContentTooBig,
+            "FIELDS_FORMAT_INVALID" => ApiError::FieldsFormatInvalid,
+            "PAGE_ACCESS_DENIED" => ApiError::PageAccessDenied,
+            "PAGE_NOT_FOUND" => ApiError::PageNotFound,
+            "PAGE_SAVE_FAILED" => ApiError::PageSaveFailed,
+            "SHORT_NAME_REQUIRED" => ApiError::ShortNameRequired,
+            "SHORT_NAME_TOO_LONG" => ApiError::ShortNameTooLong,
+            "TITLE_REQUIRED" => ApiError::TitleRequired,
+            "TITLE_TOO_LONG" => ApiError::TitleTooLong,
+            _ => match code.strip_prefix("FLOOD_WAIT_").and_then(|secs| secs.parse().ok()) {
+                Some(secs) => ApiError::FloodWait(secs),
+                None => ApiError::Unknown(code.into()),
+            },
+        }
+    }
+
+    /// The code as sent by the server.
+    pub fn code(&self) -> String {
+        let code = match self {
+            ApiError::AccessTokenInvalid => "ACCESS_TOKEN_INVALID",
+            ApiError::AuthorNameTooLong => "AUTHOR_NAME_TOO_LONG",
+            ApiError::AuthorUrlTooLong => "AUTHOR_URL_TOO_LONG",
+            ApiError::ContentFormatInvalid => "CONTENT_FORMAT_INVALID",
+            ApiError::ContentRequired => "CONTENT_REQUIRED",
+            ApiError::ContentTextRequired => "CONTENT_TEXT_REQUIRED",
+            ApiError::ContentTooBig => "CONTENT_TOO_BIG",
+            ApiError::FieldsFormatInvalid => "FIELDS_FORMAT_INVALID",
+            ApiError::PageAccessDenied => "PAGE_ACCESS_DENIED",
+            ApiError::PageNotFound => "PAGE_NOT_FOUND",
+            ApiError::PageSaveFailed => "PAGE_SAVE_FAILED",
+            ApiError::ShortNameRequired => "SHORT_NAME_REQUIRED",
+            ApiError::ShortNameTooLong => "SHORT_NAME_TOO_LONG",
+            ApiError::TitleRequired => "TITLE_REQUIRED",
+            ApiError::TitleTooLong => "TITLE_TOO_LONG",
+            ApiError::FloodWait(secs) => return format!("FLOOD_WAIT_{}", secs),
+            ApiError::Unknown(code) => code,
+        };
+        code.into()
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.code())
+    }
+}
+
+impl std::error::Error for ApiError {}
@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod test_api_error {
+    use crate::error::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_known() {
+        let codes = ["SHORT_NAME_REQUIRED", "ACCESS_TOKEN_INVALID", "PAGE_NOT_FOUND", "CONTENT_TOO_BIG", "TITLE_TOO_LONG"];
+        for code in codes {
+            let e = ApiError::from_code(code);
+            assert!(!matches!(e, ApiError::Unknown(_)), "{} not recognized", code);
+            assert_eq!(e.code(), code);
+        }
+        assert_eq!(ApiError::from_code("SHORT_NAME_REQUIRED"), ApiError::ShortNameRequired);
+        assert_eq!(ApiError::from_code("PAGE_NOT_FOUND"), ApiError::PageNotFound);
+    }
+
+    #[test]
+    fn test_flood_wait() {
+        let e = ApiError::from_code("FLOOD_WAIT_7");
+        assert_eq!(e, ApiError::FloodWait(7));
+        assert_eq!(e.to_string(), "FLOOD_WAIT_7");
+        let e = TelegraphError::from(e);
+        assert!(e.is_retryable());
+        assert!(!e.is_auth());
+        assert_eq!(e.retry_after(), Some(Duration::from_secs(7)));
+
+        let e = ApiError::from_code("FLOOD_WAIT_");
+        assert_eq!(e, ApiError::Unknown("FLOOD_WAIT_".into()));
+    }
+
+    #[test]
+    fn test_unknown() {
+        let e = ApiError::from_code("SOMETHING_NEW");
+        assert_eq!(e, ApiError::Unknown("SOMETHING_NEW".into()));
+        assert_eq!(e.to_string(), "SOMETHING_NEW");
+        let e = TelegraphError::from(e);
+        assert!(!e.is_retryable());
+        assert!(e.retry_after().is_none());
+    }
+
+    #[test]
+    fn test_auth() {
+        let e = TelegraphError::from(ApiError::from_code("ACCESS_TOKEN_INVALID"));
+        assert!(e.is_auth());
+        assert!(!e.is_retryable());
+        assert_eq!(e.to_string(), "api error: ACCESS_TOKEN_INVALID");
+    }
+}
+
+#[cfg(test)]
+mod test_retryable {
+    use crate::error::*;
+
+    #[test]
+    fn test_http() {
+        assert!(TelegraphError::Http(500).is_retryable());
+        assert!(TelegraphError::Http(503).is_retryable());
+        assert!(TelegraphError::Http(429).is_retryable());
+        assert!(!TelegraphError::Http(400).is_retryable());
+        assert!(!TelegraphError::Http(404).is_retryable());
+        assert!(TelegraphError::Transport("connection refused".into()).is_retryable());
+        assert!(!TelegraphError::from(ValidationError::new("title", "1-256 characters", "0 characters")).is_retryable());
+    }
+}
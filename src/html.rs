@@ -0,0 +1,143 @@
+//! Conversion of HTML into content, enabled by the `html` feature.
+//!
+//! Elements are mapped the way the Telegraph editor turns its DOM into nodes: text is kept as is,
+//! only `href` and `src` attributes survive and comments are dropped. On top of that, tags the API
+//! does not know are downgraded to the closest one, e.g. `h1` to `h3`, or unwrapped, keeping their
+//! children, e.g. `span`. Unwrapped block containers, e.g. `div` and `section`, turn the text they
+//! hold into paragraphs, and whitespace around blocks, e.g. the line breaks between list items, is
+//! dropped. Tables are unwrapped the same way, each cell becoming paragraphs of its own. Blocks
+//! inside inline elements, e.g. `<b><p>x</p></b>`, are lifted out of them, and paragraphs left
+//! empty, e.g. by a stray `</p>`, are dropped.
+use html5ever::tendril::TendrilSink;
+use html5ever::{local_name, namespace_url, ns, parse_fragment, ParseOpts, QualName};
+use markup5ever_rcdom::{Handle, NodeData, RcDom};
+use crate::entity::{push_text, Attrs, Content, Node, Tag};
+use crate::error::ValidationError;
+
+/// Elements dropped along with everything inside them.
+const DROPPED: [&str; 8] = ["head", "noscript", "object", "script", "style", "svg", "template", "title"];
+
+/// Unknown block elements, whose loose inline content is wrapped in paragraphs when unwrapped.
+const BLOCK_WRAPPERS: [&str; 24] = [
+    "address", "article", "caption", "center", "dd", "details", "div", "dl", "dt", "fieldset", "footer",
+    "form", "header", "main", "nav", "section", "summary", "table", "tbody", "td", "tfoot", "th",
+    "thead", "tr",
+];
+
+impl Content {
+    /// Parse an HTML fragment, see the module documentation for how elements are mapped. Fails
+    /// like `Content::try_new` if nothing is left.
+    pub fn from_html(html: &str) -> Result<Self, ValidationError> {
+        Content::try_new(html_to_nodes(html))
+    }
+}
+
+/// The nodes of an HTML fragment, possibly none.
+pub fn html_to_nodes(html: &str) -> Vec<Node> {
+    let context = QualName::new(None, ns!(html), local_name!("body"));
+    let dom = parse_fragment(RcDom::default(), ParseOpts::default(), context, vec![]).one(html);
+    let mut nodes = Vec::new();
+    // The fragment is parsed below an `html` element standing for the context.
+    for root in dom.document.children.borrow().iter() {
+        for child in root.children.borrow().iter() {
+            convert(child, &mut nodes);
+        }
+    }
+    drop_blank(nodes)
+}
+
+fn convert(handle: &Handle, out: &mut Vec<Node>) {
+    match handle.data {
+        NodeData::Text { ref contents } => push_text(out, &contents.borrow()),
+        NodeData::Element { ref name, ref attrs, .. } => {
+            let name = name.local.to_ascii_lowercase();
+            if DROPPED.contains(&&*name) {
+                return;
+            }
+            let mut children = Vec::new();
+            for child in handle.children.borrow().iter() {
+                convert(child, &mut children);
+            }
+            let children = drop_blank(children);
+            let tag = match &*name {
+                "h1" | "h2" => Tag::H3,
+                "h5" | "h6" => Tag::H4,
+                "del" | "strike" => Tag::S,
+                "ins" => Tag::U,
+                name => match Tag::from_name(name) {
+                    Some(tag) => tag,
+                    None if BLOCK_WRAPPERS.contains(&name) => {
+                        lift_blocks(Tag::P, None, children, out);
+                        return;
+                    }
+                    None => {
+                        for child in children {
+                            match child {
+                                Node::String(text) => push_text(out, &text),
+                                node => out.push(node),
+                            }
+                        }
+                        return;
+                    }
+                },
+            };
+            let mut kept = Attrs::new();
+            for attr in attrs.borrow().iter() {
+                match &*attr.name.local {
+                    "href" => kept.href = Some(attr.value.to_string()),
+                    "src" => kept.src = Some(attr.value.to_string()),
+                    _ => {}
+                }
+            }
+            let attrs = if kept.is_empty() { None } else { Some(kept) };
+            if tag == Tag::P || (!tag.is_block() && children.iter().any(is_block)) {
+                lift_blocks(tag, attrs, children, out);
+            } else {
+                out.push(Node::element(tag, attrs, children));
+            }
+        }
+        _ => {}
+    }
+}
+
+fn is_block(node: &Node) -> bool {
+    matches!(node, Node::NodeElement(element) if element.tag.is_block())
+}
+
+fn is_blank(node: &Node) -> bool {
+    matches!(node, Node::String(text) if text.chars().all(|c| c.is_ascii_whitespace()))
+}
+
+/// Drop the text nodes made of whitespace only next to a block, which browsers do not render.
+fn drop_blank(nodes: Vec<Node>) -> Vec<Node> {
+    let keep: Vec<bool> = (0..nodes.len())
+        .map(|i| {
+            let next_to_block = (i > 0 && is_block(&nodes[i - 1])) || nodes.get(i + 1).is_some_and(is_block);
+            !(is_blank(&nodes[i]) && next_to_block)
+        })
+        .collect();
+    nodes.into_iter().zip(keep).filter_map(|(node, keep)| keep.then_some(node)).collect()
+}
+
+/// Move `children` to `out`, the blocks among them as they are and each run of inline nodes in
+/// between in a `tag` element, dropped if blank. Unwrapped block elements pass a paragraph, so that
+/// their text does not merge with the text of their neighbours.
+fn lift_blocks(tag: Tag, attrs: Option<Attrs>, children: Vec<Node>, out: &mut Vec<Node>) {
+    let mut run = Vec::new();
+    for child in children {
+        if is_block(&child) {
+            flush_run(&tag, &attrs, &mut run, out);
+            out.push(child);
+        } else {
+            run.push(child);
+        }
+    }
+    flush_run(&tag, &attrs, &mut run, out);
+}
+
+fn flush_run(tag: &Tag, attrs: &Option<Attrs>, run: &mut Vec<Node>, out: &mut Vec<Node>) {
+    let run = std::mem::take(run);
+    if !run.iter().all(is_blank) {
+        out.push(Node::element(tag.clone(), attrs.clone(), run));
+    }
+}
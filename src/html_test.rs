@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod test_from_html {
+    use crate::entity::*;
+    use crate::html::*;
+
+    fn json(html: &str) -> String {
+        serde_json::to_string(&html_to_nodes(html)).unwrap()
+    }
+
+    #[test]
+    fn test_normal() {
+        let content = Content::from_html(r#"<p>Hello, <b>world</b>!</p><figure><img src="/file/1.jpg"><figcaption>Caption</figcaption></figure>"#).unwrap();
+        let s1 = r#"[{"tag":"p","children":["Hello, ",{"tag":"b","children":["world"]},"!"]},{"tag":"figure","children":[{"tag":"img","attrs":{"src":"/file/1.jpg"}},{"tag":"figcaption","children":["Caption"]}]}]"#;
+        assert_eq!(serde_json::to_string(&content).unwrap(), s1);
+        assert!(content.validate().is_ok());
+    }
+
+    #[test]
+    fn test_downgrade() {
+        assert_eq!(json("<h1>a</h1><h2>b</h2><h5>c</h5><h6>d</h6>"), r#"[{"tag":"h3","children":["a"]},{"tag":"h3","children":["b"]},{"tag":"h4","children":["c"]},{"tag":"h4","children":["d"]}]"#);
+        assert_eq!(json("<del>a</del><strike>b</strike><ins>c</ins><strong>d</strong><em>e</em><i>f</i>"), r#"[{"tag":"s","children":["a"]},{"tag":"s","children":["b"]},{"tag":"u","children":["c"]},{"tag":"strong","children":["d"]},{"tag":"em","children":["e"]},{"tag":"i","children":["f"]}]"#);
+    }
+
+    #[test]
+    fn test_unwrap() {
+        let s1 = r#"<div class="post"><p>one <span style="color:red">two</span> three</p><section><h3 id="x">Title</h3></section></div>"#;
+        assert_eq!(json(s1), r#"[{"tag":"p","children":["one two three"]},{"tag":"h3","children":["Title"]}]"#);
+        let s2 = r#"<a href="https://telegra.ph/" class="link" target="_blank">link</a><!-- note --><script>alert(1)</script>"#;
+        assert_eq!(json(s2), r#"[{"tag":"a","attrs":{"href":"https://telegra.ph/"},"children":["link"]}]"#);
+    }
+
+    #[test]
+    fn test_block_wrappers() {
+        assert_eq!(json("<div>Hello</div><div>World</div>"), r#"[{"tag":"p","children":["Hello"]},{"tag":"p","children":["World"]}]"#);
+        let s1 = "<section>intro <b>bold</b><p>para</p>outro</section><article><div>nested</div></article>";
+        assert_eq!(json(s1), r#"[{"tag":"p","children":["intro ",{"tag":"b","children":["bold"]}]},{"tag":"p","children":["para"]},{"tag":"p","children":["outro"]},{"tag":"p","children":["nested"]}]"#);
+        assert_eq!(json("<span>a</span><span>b</span>"), r#"["ab"]"#);
+    }
+
+    #[test]
+    fn test_tables() {
+        assert_eq!(json("<table><tr><td>a</td><td>b</td></tr></table>"), r#"[{"tag":"p","children":["a"]},{"tag":"p","children":["b"]}]"#);
+        let s1 = "<table>\n<caption>Totals</caption>\n<thead><tr><th>Year</th></tr></thead>\n<tbody><tr><td><b>2024</b></td></tr></tbody>\n</table>";
+        assert_eq!(json(s1), r#"[{"tag":"p","children":["Totals"]},{"tag":"p","children":["Year"]},{"tag":"p","children":[{"tag":"b","children":["2024"]}]}]"#);
+    }
+
+    #[test]
+    fn test_empty_paragraphs() {
+        assert_eq!(json("<p>a<div>b</div></p>"), r#"[{"tag":"p","children":["a"]},{"tag":"p","children":["b"]}]"#);
+        assert_eq!(json("<p> </p><p></p>"), "[]");
+    }
+
+    #[test]
+    fn test_lifted_blocks() {
+        assert_eq!(json("<b><p>x</p></b>"), r#"[{"tag":"p","children":["x"]}]"#);
+        assert_eq!(json("<em>a<h2>b</h2>c</em>"), r#"[{"tag":"em","children":["a"]},{"tag":"h3","children":["b"]},{"tag":"em","children":["c"]}]"#);
+        let s1 = r#"<p><a href="/a">one<ul><li>two</li></ul>three</a></p>"#;
+        let content = Content::from_html(s1).unwrap();
+        assert_eq!(serde_json::to_string(&content).unwrap(), r#"[{"tag":"p","children":[{"tag":"a","attrs":{"href":"/a"},"children":["one"]}]},{"tag":"ul","children":[{"tag":"li","children":[{"tag":"a","attrs":{"href":"/a"},"children":["two"]}]}]},{"tag":"a","attrs":{"href":"/a"},"children":["three"]}]"#);
+        assert!(Content::from_html("<u><blockquote>quote</blockquote></u>").unwrap().validate().is_ok());
+    }
+
+    #[test]
+    fn test_whitespace() {
+        let s1 = "<p>one</p>\n<ul>\n  <li>a</li>\n  <li>b</li>\n</ul>\n<div>\n  <p>two</p>\n</div>\n";
+        assert_eq!(json(s1), r#"[{"tag":"p","children":["one"]},{"tag":"ul","children":[{"tag":"li","children":["a"]},{"tag":"li","children":["b"]}]},{"tag":"p","children":["two"]}]"#);
+        assert_eq!(json("<p><b>a</b> <i>b</i></p>"), r#"[{"tag":"p","children":[{"tag":"b","children":["a"]}," ",{"tag":"i","children":["b"]}]}]"#);
+    }
+
+    #[test]
+    fn test_text() {
+        assert_eq!(json("a &amp; b &lt;c&gt;<br>d"), r#"["a & b <c>",{"tag":"br"},"d"]"#);
+        assert!(Content::from_html("<div></div>").is_err());
+    }
+}
@@ -6,12 +6,66 @@
 //! error is explained in the error field (e.g. SHORT_NAME_REQUIRED). All queries must be made
 //! using UTF-8.
 //!
+#[cfg(feature = "ureq")]
+pub mod blocking;
+pub mod builder;
+#[cfg(feature = "reqwest")]
+pub mod client;
+pub mod commonmark;
 pub mod entity;
+pub mod error;
+#[cfg(feature = "html")]
+pub mod html;
+pub mod limits;
+mod macros;
+#[cfg(feature = "markdown")]
+pub mod markdown;
 pub mod methods;
+pub mod render;
+pub mod request;
+pub mod split;
+pub mod transport;
+pub mod traverse;
+pub mod validate;
+#[cfg(all(test, feature = "ureq"))]
+pub(crate) mod blocking_test;
+#[cfg(test)]
+pub(crate) mod builder_test;
+#[cfg(all(test, feature = "reqwest"))]
+pub(crate) mod client_test;
+#[cfg(test)]
+pub(crate) mod commonmark_test;
 #[cfg(test)]
 pub(crate) mod entity_test;
 #[cfg(test)]
+pub(crate) mod error_test;
+#[cfg(all(test, feature = "html"))]
+pub(crate) mod html_test;
+#[cfg(test)]
+pub(crate) mod limits_test;
+#[cfg(test)]
+pub(crate) mod macros_test;
+#[cfg(all(test, feature = "markdown"))]
+pub(crate) mod markdown_test;
+#[cfg(test)]
 pub(crate) mod methods_test;
+#[cfg(test)]
+pub(crate) mod render_test;
+#[cfg(test)]
+pub(crate) mod request_test;
+#[cfg(test)]
+pub(crate) mod validate_test;
+#[cfg(test)]
+pub(crate) mod split_test;
+#[cfg(all(test, any(feature = "reqwest", feature = "ureq")))]
+pub(crate) mod test_server;
+#[cfg(test)]
+pub(crate) mod traverse_test;
 
 pub use entity::*;
+pub use error::*;
 pub use methods::*;
+pub use request::*;
+pub use transport::*;
+pub use traverse::*;
+pub use validate::*;
@@ -0,0 +1,53 @@
+//! Limits the API documents for its parameters. Text is measured in Unicode characters, as the
+//! documentation does, and both ends of every range are included.
+use crate::error::ValidationError;
+
+/// Range of characters a text parameter accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextLimit {
+    pub field: &'static str,
+    pub min: usize,
+    pub max: usize,
+}
+
+impl TextLimit {
+    pub fn check(&self, value: &str) -> Result<(), ValidationError> {
+        let len = value.chars().count();
+        if len < self.min || len > self.max {
+            return Err(ValidationError::new(self.field, format!("{}-{} characters", self.min, self.max), format!("{} characters", len)));
+        }
+        Ok(())
+    }
+}
+
+/// Range of values a numeric parameter accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberLimit {
+    pub field: &'static str,
+    pub min: u32,
+    pub max: u32,
+}
+
+impl NumberLimit {
+    pub fn check(&self, value: impl Into<u32>) -> Result<(), ValidationError> {
+        let value = value.into();
+        if value < self.min || value > self.max {
+            return Err(ValidationError::new(self.field, format!("{}-{}", self.min, self.max), value.to_string()));
+        }
+        Ok(())
+    }
+}
+
+pub const SHORT_NAME: TextLimit = TextLimit { field: "short_name", min: 1, max: 32 };
+pub const AUTHOR_NAME: TextLimit = TextLimit { field: "author_name", min: 0, max: 128 };
+pub const AUTHOR_URL: TextLimit = TextLimit { field: "author_url", min: 0, max: 512 };
+pub const TITLE: TextLimit = TextLimit { field: "title", min: 1, max: 256 };
+
+/// Largest `content` parameter accepted, in bytes of JSON.
+pub const CONTENT_SIZE: usize = 64 * 1024;
+
+pub const LIMIT: NumberLimit = NumberLimit { field: "limit", min: 0, max: 200 };
+pub const YEAR: NumberLimit = NumberLimit { field: "year", min: 2000, max: 2100 };
+pub const MONTH: NumberLimit = NumberLimit { field: "month", min: 1, max: 12 };
+pub const DAY: NumberLimit = NumberLimit { field: "day", min: 1, max: 31 };
+pub const HOUR: NumberLimit = NumberLimit { field: "hour", min: 0, max: 24 };
@@ -0,0 +1,97 @@
+#[cfg(test)]
+mod test_text_limit {
+    use crate::entity::*;
+    use crate::limits::*;
+
+    #[test]
+    fn test_bounds() {
+        assert!(SHORT_NAME.check("").is_err());
+        assert!(SHORT_NAME.check("a").is_ok());
+        assert!(SHORT_NAME.check(&"a".repeat(32)).is_ok());
+        assert!(SHORT_NAME.check(&"a".repeat(33)).is_err());
+        assert!(AUTHOR_NAME.check("").is_ok());
+        assert!(AUTHOR_NAME.check(&"a".repeat(128)).is_ok());
+        assert!(AUTHOR_NAME.check(&"a".repeat(129)).is_err());
+        assert!(AUTHOR_URL.check(&"a".repeat(512)).is_ok());
+        assert!(AUTHOR_URL.check(&"a".repeat(513)).is_err());
+        assert!(TITLE.check("").is_err());
+        assert!(TITLE.check(&"a".repeat(256)).is_ok());
+        assert!(TITLE.check(&"a".repeat(257)).is_err());
+    }
+
+    #[test]
+    fn test_multi_byte() {
+        // 32 Cyrillic letters take 64 bytes but are still 32 characters.
+        let name = "Ж".repeat(32);
+        assert_eq!(name.len(), 64);
+        assert!(ShortName::try_new(name).is_ok());
+        let e = ShortName::try_new("Ж".repeat(33)).err().unwrap();
+        assert_eq!(e.to_string(), "short_name: expected 1-32 characters, got 33 characters");
+        assert!(AuthorName::try_new("Анна Каренина".repeat(9)).is_ok());
+        assert!(Title::try_new("🦀".repeat(256)).is_ok());
+        assert!(Title::try_new("🦀".repeat(257)).is_err());
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let s1 = format!("\"{}\"", "Ж".repeat(128));
+        let d1: Result<AuthorName, _> = serde_json::from_str(&s1);
+        assert!(d1.is_ok());
+        let s2 = format!("\"{}\"", "Ж".repeat(129));
+        let d2: Result<AuthorName, _> = serde_json::from_str(&s2);
+        let e = d2.err().unwrap().to_string();
+        assert!(e.starts_with("author_name: expected 0-128 characters, got 129 characters"), "unexpected error: {}", e);
+        let d3: Result<Title, _> = serde_json::from_str(&format!("\"{}\"", "é".repeat(256)));
+        assert!(d3.is_ok());
+    }
+}
+
+#[cfg(test)]
+mod test_number_limit {
+    use crate::limits::*;
+
+    #[test]
+    fn test_bounds() {
+        assert!(LIMIT.check(0u8).is_ok());
+        assert!(LIMIT.check(201u16).is_err());
+        assert!(YEAR.check(2000u16).is_ok());
+        assert!(YEAR.check(2101u16).is_err());
+        assert!(MONTH.check(0u8).is_err());
+        assert!(DAY.check(31u8).is_ok());
+        assert_eq!(HOUR.check(25u8).err().unwrap().to_string(), "hour: expected 0-24, got 25");
+    }
+}
+
+#[cfg(test)]
+mod test_content_size {
+    use crate::entity::*;
+    use crate::limits::*;
+
+    #[test]
+    fn test_encoded_size() {
+        let content = Content::new(crate::content![p["Привет, \"world\"\n", a(href = "https://telegra.ph/")["<link>"]], hr]);
+        let json = serde_json::to_string(&content).unwrap();
+        assert_eq!(content.encoded_size(), json.len());
+        assert_eq!(content.0[1].len(), r#"{"tag":"hr"}"#.len());
+        assert_eq!(Node::text("é").len(), 4);
+    }
+
+    #[test]
+    fn test_limit() {
+        // `["` and `"]` take 4 bytes around the text.
+        let content = Content::try_new(vec![Node::text("a".repeat(CONTENT_SIZE - 4))]).unwrap();
+        assert_eq!(content.encoded_size(), CONTENT_SIZE);
+        let e = Content::try_new(vec![Node::text("ж".repeat(CONTENT_SIZE / 2))]).err().unwrap();
+        assert_eq!(e.to_string(), "content: expected at most 65536 bytes, got 65540 bytes");
+        let e = Content::try_new(vec![]).err().unwrap();
+        assert_eq!(e.to_string(), "content: expected at least 1 node, got 0 nodes");
+    }
+
+    #[test]
+    fn test_page_methods() {
+        let mut content = Content::new(vec![Node::text("text")]);
+        content.0.push(Node::text("a".repeat(CONTENT_SIZE)));
+        let e = crate::methods::CreatePage::try_new("1234567".into(), "title".into(), content, false).err().unwrap();
+        assert_eq!(e.actual, "65547 bytes");
+    }
+}
@@ -0,0 +1,148 @@
+//! Declarative construction of content trees, close to the HTML they stand for.
+
+/// Build a `Vec<Node>` from a list of text literals and elements, see `node!` for the syntax of
+/// each item. `{expr}` splices anything implementing `builder::IntoNodes`.
+///
+/// ```
+/// use telegra_ph::{content, Content};
+///
+/// let name = "world";
+/// let nodes = content![
+///     h3["Intro"],
+///     p["Hello ", b[{name}], ", see ", a(href = "https://telegra.ph/")["this"], br],
+///     figure[img(src = "/file/1.jpg"), figcaption["Caption"]],
+/// ];
+/// assert_eq!(Content::new(nodes).0.len(), 3);
+/// ```
+///
+/// Tags and attributes are checked when compiling:
+///
+/// ```compile_fail
+/// let nodes = telegra_ph::content![h2["Intro"]];
+/// ```
+///
+/// ```compile_fail
+/// let nodes = telegra_ph::content![a(class = "x")["link"]];
+/// ```
+#[macro_export]
+macro_rules! content {
+    ($($body:tt)*) => {{
+        let mut nodes: ::std::vec::Vec<$crate::entity::Node> = ::std::vec::Vec::new();
+        #[allow(unused_variables)]
+        let out = &mut nodes;
+        $crate::__nodes!(out; $($body)*);
+        nodes
+    }};
+}
+
+/// Build a single `Node`: a text literal, or `tag`, `tag(attr = value, ...)`, `tag[children]` or
+/// `tag(attr = value, ...)[children]` where children follow the syntax of `content!`.
+///
+/// ```
+/// use telegra_ph::{node, Node, Tag};
+///
+/// let link = node!(a(href = "https://telegra.ph/")["Telegraph"]);
+/// assert!(matches!(link, Node::NodeElement(ref e) if e.tag == Tag::A));
+/// ```
+#[macro_export]
+macro_rules! node {
+    ($text:literal) => {
+        $crate::entity::Node::text($text)
+    };
+    ($tag:ident $( ( $($attr:ident = $val:expr),* $(,)? ) )? $( [ $($children:tt)* ] )?) => {
+        $crate::entity::Node::element(
+            $crate::__tag!($tag),
+            $crate::__attrs!($($($attr = $val),*)?),
+            $crate::content![$($($children)*)?],
+        )
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __nodes {
+    ($nodes:ident;) => {};
+    ($nodes:ident; $text:literal $(, $($rest:tt)*)?) => {
+        $nodes.push($crate::entity::Node::text($text));
+        $($crate::__nodes!($nodes; $($rest)*);)?
+    };
+    ($nodes:ident; { $e:expr } $(, $($rest:tt)*)?) => {
+        $nodes.extend($crate::builder::IntoNodes::into_nodes($e));
+        $($crate::__nodes!($nodes; $($rest)*);)?
+    };
+    ($nodes:ident; $tag:ident ( $($attrs:tt)* ) [ $($children:tt)* ] $(, $($rest:tt)*)?) => {
+        $nodes.push($crate::node!($tag ( $($attrs)* ) [ $($children)* ]));
+        $($crate::__nodes!($nodes; $($rest)*);)?
+    };
+    ($nodes:ident; $tag:ident ( $($attrs:tt)* ) $(, $($rest:tt)*)?) => {
+        $nodes.push($crate::node!($tag ( $($attrs)* )));
+        $($crate::__nodes!($nodes; $($rest)*);)?
+    };
+    ($nodes:ident; $tag:ident [ $($children:tt)* ] $(, $($rest:tt)*)?) => {
+        $nodes.push($crate::node!($tag [ $($children)* ]));
+        $($crate::__nodes!($nodes; $($rest)*);)?
+    };
+    ($nodes:ident; $tag:ident $(, $($rest:tt)*)?) => {
+        $nodes.push($crate::node!($tag));
+        $($crate::__nodes!($nodes; $($rest)*);)?
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __attrs {
+    () => {
+        ::std::option::Option::None
+    };
+    ($($attr:ident = $val:expr),+) => {{
+        let mut attrs = $crate::entity::Attrs::new();
+        $( $crate::__attr!(attrs, $attr, $val); )+
+        ::std::option::Option::Some(attrs)
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __attr {
+    ($attrs:ident, href, $val:expr) => {
+        $attrs.href = ::std::option::Option::Some(::std::convert::Into::into($val))
+    };
+    ($attrs:ident, src, $val:expr) => {
+        $attrs.src = ::std::option::Option::Some(::std::convert::Into::into($val))
+    };
+    ($attrs:ident, $other:ident, $val:expr) => {
+        ::std::compile_error!(::std::concat!("unknown attribute `", ::std::stringify!($other), "`, expected href or src"))
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __tag {
+    (a) => { $crate::entity::Tag::A };
+    (aside) => { $crate::entity::Tag::Aside };
+    (b) => { $crate::entity::Tag::B };
+    (blockquote) => { $crate::entity::Tag::Blockquote };
+    (br) => { $crate::entity::Tag::Br };
+    (code) => { $crate::entity::Tag::Code };
+    (em) => { $crate::entity::Tag::Em };
+    (figcaption) => { $crate::entity::Tag::Figcaption };
+    (figure) => { $crate::entity::Tag::Figure };
+    (h3) => { $crate::entity::Tag::H3 };
+    (h4) => { $crate::entity::Tag::H4 };
+    (hr) => { $crate::entity::Tag::Hr };
+    (i) => { $crate::entity::Tag::I };
+    (iframe) => { $crate::entity::Tag::Iframe };
+    (img) => { $crate::entity::Tag::Img };
+    (li) => { $crate::entity::Tag::Li };
+    (ol) => { $crate::entity::Tag::Ol };
+    (p) => { $crate::entity::Tag::P };
+    (pre) => { $crate::entity::Tag::Pre };
+    (s) => { $crate::entity::Tag::S };
+    (strong) => { $crate::entity::Tag::Strong };
+    (u) => { $crate::entity::Tag::U };
+    (ul) => { $crate::entity::Tag::Ul };
+    (video) => { $crate::entity::Tag::Video };
+    ($other:ident) => {
+        ::std::compile_error!(::std::concat!("unknown tag `", ::std::stringify!($other), "`"))
+    };
+}
@@ -0,0 +1,45 @@
+#[cfg(test)]
+mod test_content {
+    use crate::builder::*;
+    use crate::entity::*;
+
+    #[test]
+    fn test_normal() {
+        let nodes = crate::content![
+            h3["Intro"],
+            p["Hello ", b["world"], a(href = "https://x")["link"]],
+        ];
+        let expected = ContentBuilder::new()
+            .h3("Intro")
+            .p(Inline::new().text("Hello ").b("world").a("https://x", "link"))
+            .build()
+            .unwrap();
+        assert_eq!(Content::new(nodes), expected);
+    }
+
+    #[test]
+    fn test_forms() {
+        let caption = String::from("Caption");
+        let items = vec![Node::text("two")];
+        let nodes = crate::content![
+            hr,
+            figure[img(src = "/file/1.jpg"), figcaption[{caption}]],
+            ul[li["one"], li[{items}],],
+            p[{Inline::new().i("x").br()}],
+            "text",
+        ];
+        let s1 = r#"[{"tag":"hr"},{"tag":"figure","children":[{"tag":"img","attrs":{"src":"/file/1.jpg"}},{"tag":"figcaption","children":["Caption"]}]},{"tag":"ul","children":[{"tag":"li","children":["one"]},{"tag":"li","children":["two"]}]},{"tag":"p","children":[{"tag":"i","children":["x"]},{"tag":"br"}]},"text"]"#;
+        assert_eq!(serde_json::to_string(&nodes).unwrap(), s1);
+        assert!(crate::content![].is_empty());
+    }
+
+    #[test]
+    fn test_node() {
+        assert_eq!(crate::node!("text"), Node::text("text"));
+        let href = "https://telegra.ph/".to_string();
+        let node = crate::node!(a(href = href.clone(), src = "/x"));
+        let expected = Node::element(Tag::A, Some(Attrs { href: Some(href), src: Some("/x".into()), ..Attrs::default() }), vec![]);
+        assert_eq!(node, expected);
+        assert_eq!(crate::node!(br), Node::element(Tag::Br, None, vec![]));
+    }
+}
@@ -0,0 +1,135 @@
+//! Conversion of Markdown into content, enabled by the `markdown` feature.
+//!
+//! Headings become `h3` up to level 3 and `h4` below, fenced and indented code blocks `pre`, and
+//! images a `figure` with the alt text as `figcaption`. Paragraphs inside list items and block
+//! quotes are unwrapped, separated by `br`, and images split the paragraph, heading, link or
+//! emphasis they are in, which is repeated on each side, so the result always passes
+//! `Content::validate`. Raw HTML is kept as text.
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag as MdTag};
+use crate::entity::{push_text, Attrs, Content, Node, Tag};
+use crate::error::ValidationError;
+use crate::traverse::text_of;
+
+impl Content {
+    /// Parse CommonMark with strikethrough, see the module documentation for how it is mapped.
+    /// Fails like `Content::try_new` if nothing is left.
+    pub fn from_markdown(markdown: &str) -> Result<Self, ValidationError> {
+        Content::try_new(markdown_to_nodes(markdown))
+    }
+}
+
+enum Kind {
+    Element(Tag, Option<Attrs>),
+    Paragraph,
+    Image(String),
+    Unwrap,
+}
+
+struct Frame {
+    kind: Kind,
+    children: Vec<Node>,
+}
+
+/// The nodes of a Markdown document, possibly none.
+pub fn markdown_to_nodes(markdown: &str) -> Vec<Node> {
+    let mut stack = vec![Frame { kind: Kind::Unwrap, children: Vec::new() }];
+    for event in Parser::new_ext(markdown, Options::ENABLE_STRIKETHROUGH) {
+        let children = &mut stack.last_mut().expect("root frame is never closed").children;
+        match event {
+            Event::Start(tag) => {
+                let kind = match tag {
+                    MdTag::Paragraph => Kind::Paragraph,
+                    MdTag::Heading { level, .. } if level <= HeadingLevel::H3 => Kind::Element(Tag::H3, None),
+                    MdTag::Heading { .. } => Kind::Element(Tag::H4, None),
+                    MdTag::BlockQuote(_) => Kind::Element(Tag::Blockquote, None),
+                    MdTag::CodeBlock(_) => Kind::Element(Tag::Pre, None),
+                    MdTag::List(None) => Kind::Element(Tag::Ul, None),
+                    MdTag::List(Some(_)) => Kind::Element(Tag::Ol, None),
+                    MdTag::Item => Kind::Element(Tag::Li, None),
+                    MdTag::Emphasis => Kind::Element(Tag::Em, None),
+                    MdTag::Strong => Kind::Element(Tag::Strong, None),
+                    MdTag::Strikethrough => Kind::Element(Tag::S, None),
+                    MdTag::Link { dest_url, .. } => Kind::Element(Tag::A, Some(Attrs::href(dest_url.to_string()))),
+                    MdTag::Image { dest_url, .. } => Kind::Image(dest_url.to_string()),
+                    _ => Kind::Unwrap,
+                };
+                stack.push(Frame { kind, children: Vec::new() });
+            }
+            Event::End(_) => {
+                let frame = stack.pop().expect("events are balanced");
+                let parent = stack.last_mut().expect("root frame is never closed");
+                close(frame, parent);
+            }
+            Event::Text(text) | Event::Html(text) | Event::InlineHtml(text) => push_text(children, &text),
+            Event::Code(code) => children.push(Node::element(Tag::Code, None, vec![Node::text(code.to_string())])),
+            Event::SoftBreak => push_text(children, "\n"),
+            Event::HardBreak => children.push(Node::element(Tag::Br, None, vec![])),
+            Event::Rule => children.push(Node::element(Tag::Hr, None, vec![])),
+            _ => {}
+        }
+    }
+    stack.pop().map(|root| root.children).unwrap_or_default()
+}
+
+fn close(frame: Frame, parent: &mut Frame) {
+    match frame.kind {
+        // Figures are blocks, so they end the inline elements and headings they are in, inner
+        // elements being closed first lifts them up to the paragraph.
+        Kind::Element(tag, attrs) if (!tag.is_block() || matches!(tag, Tag::H3 | Tag::H4)) && frame.children.iter().any(is_figure) => {
+            lift_figures(tag, attrs, frame.children, &mut parent.children);
+        }
+        Kind::Element(tag, attrs) => parent.children.push(Node::element(tag, attrs, frame.children)),
+        Kind::Image(src) => {
+            let mut children = vec![Node::element(Tag::Img, Some(Attrs::src(src)), vec![])];
+            let alt = text_of(&frame.children);
+            if !alt.is_empty() {
+                children.push(Node::element(Tag::Figcaption, None, vec![Node::String(alt)]));
+            }
+            parent.children.push(Node::element(Tag::Figure, None, children));
+        }
+        Kind::Paragraph if matches!(parent.kind, Kind::Element(Tag::Li | Tag::Blockquote, _)) => {
+            if !parent.children.is_empty() {
+                parent.children.push(Node::element(Tag::Br, None, vec![]));
+            }
+            append(&mut parent.children, frame.children);
+        }
+        Kind::Paragraph => lift_figures(Tag::P, None, frame.children, &mut parent.children),
+        Kind::Unwrap => append(&mut parent.children, frame.children),
+    }
+}
+
+fn is_figure(node: &Node) -> bool {
+    matches!(node, Node::NodeElement(e) if e.tag == Tag::Figure)
+}
+
+/// Push `children` to `out` in a `tag` element, ended before each figure and started again after
+/// it, the figures in between.
+fn lift_figures(tag: Tag, attrs: Option<Attrs>, children: Vec<Node>, out: &mut Vec<Node>) {
+    let mut run = Vec::new();
+    for node in children {
+        if is_figure(&node) {
+            flush_run(&tag, &attrs, &mut run, out);
+            out.push(node);
+        } else {
+            run.push(node);
+        }
+    }
+    flush_run(&tag, &attrs, &mut run, out);
+}
+
+fn flush_run(tag: &Tag, attrs: &Option<Attrs>, run: &mut Vec<Node>, out: &mut Vec<Node>) {
+    let blank = run.iter().all(|node| matches!(node, Node::String(text) if text.trim().is_empty()));
+    let run = std::mem::take(run);
+    if !blank {
+        out.push(Node::element(tag.clone(), attrs.clone(), run));
+    }
+}
+
+fn append(out: &mut Vec<Node>, nodes: Vec<Node>) {
+    for node in nodes {
+        match node {
+            Node::String(text) => push_text(out, &text),
+            node => out.push(node),
+        }
+    }
+}
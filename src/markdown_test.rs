@@ -0,0 +1,314 @@
+#[cfg(test)]
+mod test_from_markdown {
+    use crate::entity::*;
+    use crate::markdown::*;
+
+    fn json(markdown: &str) -> String {
+        serde_json::to_string(&markdown_to_nodes(markdown)).unwrap()
+    }
+
+    #[test]
+    fn test_blocks() {
+        let s1 = "# One\n\n## Two\n\n### Three\n\n#### Four\n\n###### Six\n\nText with *em*, **strong**, ~~gone~~ and `code`.\n\n---\n\n> quoted\n";
+        let expected = r#"[{"tag":"h3","children":["One"]},{"tag":"h3","children":["Two"]},{"tag":"h3","children":["Three"]},{"tag":"h4","children":["Four"]},{"tag":"h4","children":["Six"]},{"tag":"p","children":["Text with ",{"tag":"em","children":["em"]},", ",{"tag":"strong","children":["strong"]},", ",{"tag":"s","children":["gone"]}," and ",{"tag":"code","children":["code"]},"."]},{"tag":"hr"},{"tag":"blockquote","children":["quoted"]}]"#;
+        assert_eq!(json(s1), expected);
+        assert_eq!(json("```rust\nfn main() {}\n```\n"), r#"[{"tag":"pre","children":["fn main() {}\n"]}]"#);
+    }
+
+    #[test]
+    fn test_lists() {
+        let s1 = "- one\n- two\n  1. a\n  2. b\n- three\n";
+        let expected = r#"[{"tag":"ul","children":[{"tag":"li","children":["one"]},{"tag":"li","children":["two",{"tag":"ol","children":[{"tag":"li","children":["a"]},{"tag":"li","children":["b"]}]}]},{"tag":"li","children":["three"]}]}]"#;
+        assert_eq!(json(s1), expected);
+        let s2 = "1. first\n\n   more\n\n2. second\n";
+        assert_eq!(json(s2), r#"[{"tag":"ol","children":[{"tag":"li","children":["first",{"tag":"br"},"more"]},{"tag":"li","children":["second"]}]}]"#);
+    }
+
+    #[test]
+    fn test_links_and_images() {
+        let s1 = "See [the *docs*](https://telegra.ph/api) now.\n\nBefore ![A **cat**](/file/cat.jpg) after\n\n![](/file/dog.jpg)\n";
+        let expected = r#"[{"tag":"p","children":["See ",{"tag":"a","attrs":{"href":"https://telegra.ph/api"},"children":["the ",{"tag":"em","children":["docs"]}]}," now."]},{"tag":"p","children":["Before "]},{"tag":"figure","children":[{"tag":"img","attrs":{"src":"/file/cat.jpg"}},{"tag":"figcaption","children":["A cat"]}]},{"tag":"p","children":[" after"]},{"tag":"figure","children":[{"tag":"img","attrs":{"src":"/file/dog.jpg"}}]}]"#;
+        assert_eq!(json(s1), expected);
+    }
+
+    #[test]
+    fn test_nested_images() {
+        let figure = r#"{"tag":"figure","children":[{"tag":"img","attrs":{"src":"/i.jpg"}},{"tag":"figcaption","children":["alt"]}]}"#;
+        assert_eq!(json("[![alt](/i.jpg)](https://x)\n"), format!("[{}]", figure));
+        let s1 = "a [*b ![alt](/i.jpg) c*](https://x) d\n";
+        assert_eq!(json(s1), format!(r#"[{{"tag":"p","children":["a ",{{"tag":"a","attrs":{{"href":"https://x"}},"children":[{{"tag":"em","children":["b "]}}]}}]}},{},{{"tag":"p","children":[{{"tag":"a","attrs":{{"href":"https://x"}},"children":[{{"tag":"em","children":[" c"]}}]}}," d"]}}]"#, figure));
+        assert_eq!(json("# head ![alt](/i.jpg)\n"), format!(r#"[{{"tag":"h3","children":["head "]}},{}]"#, figure));
+    }
+
+    #[test]
+    fn test_emphasis_delimiters() {
+        let s1 = "**ab** *c*__d__*e*\n\na **b** c  *x*  ~~y~~ z\n\na<strong>.b</strong> __*c* d__\n";
+        let expected = Content::new(crate::content![
+            p[strong["ab"], " ", em["c"], strong["d"], em["e"]],
+            p["a ", strong["b"], " c  ", em["x"], "  ", s["y"], " z"],
+            p["a<strong>.b</strong> ", strong[em["c"], " d"]],
+        ]);
+        assert_eq!(Content::from_markdown(s1).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_valid() {
+        let s1 = "# Title\n\n> a\n>\n> b\n\n* x\n  ![img](/1.jpg)\n\nline  \nbreak <span>raw</span>\n\n<div>block</div>\n";
+        let content = Content::from_markdown(s1).unwrap();
+        assert_eq!(content.validate(), Ok(()));
+        let s2 = "#### **[x ![a](/b.jpg)](/c)**\n\n> _![a](/b.jpg)_\n\n* [![a](/b.jpg)](/c)\n";
+        let content = Content::from_markdown(s2).unwrap();
+        assert_eq!(content.validate(), Ok(()));
+        assert!(Content::from_markdown("").is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_round_trip {
+    use proptest::prelude::*;
+    use crate::entity::*;
+
+    fn word() -> impl Strategy<Value = String> {
+        prop_oneof![
+            3 => "[a-zA-Z]{1,6}",
+            1 => "[a-z0-9#*_\\[\\]<>&~\\\\.!+=|-]{1,4}",
+        ]
+    }
+
+    fn words() -> impl Strategy<Value = String> {
+        prop::collection::vec(word(), 1..4).prop_map(|words| words.join(" "))
+    }
+
+    /// An inline element of `kind` around plain words: 0 strong, 1 em, 2 s, 3 code, 4 a.
+    fn leaf(kind: u8) -> impl Strategy<Value = Node> {
+        (words(), "[a-z]{1,5}( [a-z]{1,5})?", "[a-z]{1,5}").prop_map(move |(text, code, path)| match kind {
+            0 => Node::element(Tag::Strong, None, vec![Node::String(text)]),
+            1 => Node::element(Tag::Em, None, vec![Node::String(text)]),
+            2 => Node::element(Tag::S, None, vec![Node::String(text)]),
+            3 => Node::element(Tag::Code, None, vec![Node::String(code)]),
+            _ => Node::element(Tag::A, Some(Attrs::href(format!("https://example.com/{}", path))), vec![Node::String(text)]),
+        })
+    }
+
+    /// An inline element whose children start and end with words, possibly around another kind.
+    /// Emphases alone may have whitespace at either end: 1 before, 2 after, 3 both.
+    fn span() -> impl Strategy<Value = Node> {
+        (0u8..5, 0u8..4, any::<bool>(), words(), words(), prop_oneof![3 => Just(0u8), 1 => 1u8..4]).prop_flat_map(|(outer, inner, nested, before, after, pad)| {
+            let inner = if inner >= outer { inner + 1 } else { inner };
+            let nested = nested && outer != 3 && !(outer == 4 && inner == 4);
+            leaf(inner).prop_map(move |inner| {
+                if outer < 3 && !nested && pad > 0 {
+                    let lead = if pad & 1 != 0 { " " } else { "" };
+                    let trail = if pad & 2 != 0 { " " } else { "" };
+                    return wrap(outer, vec![Node::String(format!("{}{}{}", lead, before, trail))]);
+                }
+                if outer == 3 || !nested {
+                    return inner_only(outer, &before);
+                }
+                let mut children = Vec::new();
+                push_text(&mut children, &format!("{} ", before));
+                children.push(inner);
+                push_text(&mut children, &format!(" {}", after));
+                wrap(outer, children)
+            })
+        })
+    }
+
+    fn inner_only(kind: u8, text: &str) -> Node {
+        let text = if kind == 3 { text.replace(|c: char| !c.is_ascii_alphabetic(), "x") } else { text.to_string() };
+        wrap(kind, vec![Node::String(text)])
+    }
+
+    fn wrap(kind: u8, children: Vec<Node>) -> Node {
+        match kind {
+            0 => Node::element(Tag::Strong, None, children),
+            1 => Node::element(Tag::Em, None, children),
+            2 => Node::element(Tag::S, None, children),
+            3 => Node::element(Tag::Code, None, children),
+            _ => Node::element(Tag::A, Some(Attrs::href("https://example.com/")), children),
+        }
+    }
+
+    /// Words, spans, runs of adjacent spans and links right after a `!`, separated by spaces.
+    fn inline() -> impl Strategy<Value = Vec<Node>> {
+        let item = prop_oneof![
+            3 => words().prop_map(|text| vec![Node::String(text)]),
+            1 => span().prop_map(|span| vec![span]),
+            1 => prop::collection::vec(span(), 2..4),
+            1 => (words(), leaf(4)).prop_map(|(text, link)| vec![Node::String(format!("{}!", text)), link]),
+        ];
+        prop::collection::vec(item, 1..5).prop_map(|items| {
+            let mut nodes = Vec::new();
+            for (i, item) in items.into_iter().enumerate() {
+                if i > 0 {
+                    push_text(&mut nodes, " ");
+                }
+                for node in item {
+                    match node {
+                        Node::String(text) => push_text(&mut nodes, &text),
+                        node => nodes.push(node),
+                    }
+                }
+            }
+            nodes
+        })
+    }
+
+    /// Inline content, possibly ending with a `br` as the editor leaves them.
+    fn ended() -> impl Strategy<Value = Vec<Node>> {
+        (inline(), any::<bool>()).prop_map(|(mut nodes, br)| {
+            if br {
+                nodes.push(Node::element(Tag::Br, None, vec![]));
+            }
+            nodes
+        })
+    }
+
+    /// Inline content, possibly with a line break in its text.
+    fn heading() -> impl Strategy<Value = Vec<Node>> {
+        (inline(), prop::option::of(words())).prop_map(|(mut nodes, line)| {
+            if let Some(line) = line {
+                push_text(&mut nodes, &format!("\n{}", line));
+            }
+            nodes
+        })
+    }
+
+    fn is_emphasis(tag: &Tag) -> bool {
+        matches!(tag, Tag::Strong | Tag::Em | Tag::S)
+    }
+
+    /// The tree `nodes` read back as, see the documentation of `commonmark`: adjacent emphases or
+    /// code spans of the same kind merge, whitespace moves out of them, line breaks in headings become spaces
+    /// and blocks lose the spaces at either end.
+    fn normalize(nodes: &[Node], single_line: bool) -> Vec<Node> {
+        let mut out = Vec::new();
+        let mut i = 0;
+        while i < nodes.len() {
+            let element = match &nodes[i] {
+                Node::String(text) => {
+                    push_text(&mut out, &if single_line { text.replace('\n', " ") } else { text.clone() });
+                    i += 1;
+                    continue;
+                }
+                Node::NodeElement(e) => e,
+            };
+            let mut children = element.children.clone().unwrap_or_default();
+            i += 1;
+            let merges = is_emphasis(&element.tag) || element.tag == Tag::Code;
+            while merges && matches!(nodes.get(i), Some(Node::NodeElement(e)) if e.tag == element.tag) {
+                if let Node::NodeElement(e) = &nodes[i] {
+                    children.extend(e.children.clone().unwrap_or_default());
+                }
+                i += 1;
+            }
+            let single_line = single_line || matches!(element.tag, Tag::H3 | Tag::H4);
+            let mut children = normalize(&children, single_line);
+            if !is_emphasis(&element.tag) {
+                if matches!(element.tag, Tag::P | Tag::H3 | Tag::H4 | Tag::Li | Tag::Blockquote) {
+                    trim_block(&mut children);
+                }
+                out.push(Node::element(element.tag.clone(), element.attrs.clone(), children));
+                continue;
+            }
+            let mut leading = String::new();
+            if let Some(Node::String(first)) = children.first_mut() {
+                let trimmed = first.trim_start_matches([' ', '\n']).to_string();
+                leading = first[..first.len() - trimmed.len()].to_string();
+                *first = trimmed;
+            }
+            let mut trailing = String::new();
+            if let Some(Node::String(last)) = children.last_mut() {
+                let trimmed = last.trim_end_matches([' ', '\n']).to_string();
+                trailing = last[trimmed.len()..].to_string();
+                *last = trimmed;
+            }
+            children.retain(|node| !matches!(node, Node::String(text) if text.is_empty()));
+            push_text(&mut out, &leading);
+            if !children.is_empty() {
+                out.push(Node::element(element.tag.clone(), None, children));
+            }
+            push_text(&mut out, &trailing);
+        }
+        out
+    }
+
+    /// Drop the spaces at the start of each line of a block, after a `br`, and the `br` and spaces
+    /// at the end of its inline content, before a nested list.
+    fn trim_block(children: &mut Vec<Node>) {
+        let is_tag = |node: &Node, tags: &[Tag]| matches!(node, Node::NodeElement(e) if tags.contains(&e.tag));
+        let inline_end = children.iter().position(|node| is_tag(node, &[Tag::Ul, Tag::Ol])).unwrap_or(children.len());
+        if inline_end > 0 && is_tag(&children[inline_end - 1], &[Tag::Br]) {
+            children.remove(inline_end - 1);
+        }
+        for i in 0..children.len() {
+            let starts = i == 0 || is_tag(&children[i - 1], &[Tag::Br]);
+            let ends = children.get(i + 1).is_none_or(|next| is_tag(next, &[Tag::Ul, Tag::Ol]));
+            if let Node::String(text) = &mut children[i] {
+                if starts {
+                    *text = text.trim_start_matches(' ').to_string();
+                }
+                if ends {
+                    *text = text.trim_end_matches(' ').to_string();
+                }
+            }
+        }
+        children.retain(|node| !matches!(node, Node::String(text) if text.is_empty()));
+    }
+
+    fn list(depth: u32) -> BoxedStrategy<Node> {
+        let item = if depth == 0 {
+            ended().prop_map(|children| Node::element(Tag::Li, None, children)).boxed()
+        } else {
+            (ended(), prop::option::of(list(depth - 1)))
+                .prop_map(|(mut children, nested)| {
+                    children.extend(nested);
+                    Node::element(Tag::Li, None, children)
+                })
+                .boxed()
+        };
+        (any::<bool>(), prop::collection::vec(item, 1..4))
+            .prop_map(|(ordered, items)| Node::element(if ordered { Tag::Ol } else { Tag::Ul }, None, items))
+            .boxed()
+    }
+
+    fn block() -> impl Strategy<Value = Node> {
+        prop_oneof![
+            1 => heading().prop_map(|children| Node::element(Tag::H3, None, children)),
+            1 => heading().prop_map(|children| Node::element(Tag::H4, None, children)),
+            3 => ended().prop_map(|children| Node::element(Tag::P, None, children)),
+            1 => Just(Node::element(Tag::Hr, None, vec![])),
+            1 => prop::collection::vec("[a-z =;{}()]{0,10}", 1..4).prop_map(|lines| Node::element(Tag::Pre, None, vec![Node::String(lines.join("\n") + "\n")])),
+            1 => (prop::collection::vec(inline(), 1..3), any::<bool>()).prop_map(|(runs, br)| {
+                let mut children = Vec::new();
+                for (i, run) in runs.into_iter().enumerate() {
+                    if i > 0 {
+                        children.push(Node::element(Tag::Br, None, vec![]));
+                    }
+                    children.extend(run);
+                }
+                if br {
+                    children.push(Node::element(Tag::Br, None, vec![]));
+                }
+                Node::element(Tag::Blockquote, None, children)
+            }),
+            2 => list(1),
+            1 => ("[a-z]{1,6}", prop::option::of(words())).prop_map(|(name, caption)| {
+                let mut children = vec![Node::element(Tag::Img, Some(Attrs::src(format!("/file/{}.jpg", name))), vec![])];
+                children.extend(caption.map(|caption| Node::element(Tag::Figcaption, None, vec![Node::String(caption)])));
+                Node::element(Tag::Figure, None, children)
+            }),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn test_markdown_round_trip(blocks in prop::collection::vec(block(), 1..6)) {
+            let content = Content::new(blocks);
+            let markdown = content.to_markdown();
+            let parsed = Content::from_markdown(&markdown);
+            let expected = Content::new(normalize(&content.0, false));
+            prop_assert_eq!(parsed.as_ref().ok(), Some(&expected), "markdown:\n{}", markdown);
+        }
+    }
+}
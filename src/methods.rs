@@ -1,4 +1,44 @@
+#![allow(clippy::doc_overindented_list_items)]
+use std::pin::Pin;
+use std::future::Future;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use crate::entity::*;
+use crate::error::{TelegraphError, ValidationError};
+use crate::request::Request;
+use crate::transport::{self, BlockingTransport, Params, Transport};
+
+pub type Ret<T> = Result<T, TelegraphError>;
+
+/// A method of the API, tied to the type of the result it responds with. Implemented by every
+/// request of this module, so transports, mocks or retry logic can be written once for all of
+/// them, see `transport::execute`.
+pub trait TelegraphMethod {
+    /// Type of the `result` field of the response.
+    type Response: DeserializeOwned;
+    /// Name of the method, e.g. `createAccount`.
+    const METHOD: &'static str;
+
+    /// Path following the method name, e.g. `Sample-Page-12-15` for `getPage`.
+    fn path(&self) -> Option<&str> {
+        None
+    }
+
+    /// Parameters of the call, in their wire form.
+    fn params(&self) -> Params;
+
+    /// The request to send, see `Request::url` for the exact URL.
+    fn to_request(&self) -> Request {
+        Request::new(Self::METHOD, self.path().map(String::from), self.params())
+    }
+}
+
+fn is_default<T: Default + PartialEq>(value: &T) -> bool {
+    *value == T::default()
+}
+
+/// Closure driving a method by hand, see the `run` method of each request. Both the closure and
+/// the future it returns are `Send`, as is the future of `run`.
+pub type Runner<M, T> = Box<dyn for<'a> Fn(&'a mut M) -> Pin<Box<dyn Future<Output = Ret<T>> + Send + 'a>> + Send + Sync>;
 
 /// Use this method to create a new Telegraph account. Most users only need one account, but this
 /// can be useful for channel administrators who would like to keep individual author names and
@@ -17,9 +57,14 @@ use crate::entity::*;
 ///     or channel.
 /// - Sample request
 ///     <https://api.telegra.ph/createAccount?short_name=Sandbox&author_name=Anonymous>
+#[derive(Deserialize, Serialize)]
 pub struct CreateAccount {
     pub short_name: ShortName,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub author_name: Option<AuthorName>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub author_url: Option<AuthorUrl>,
 }
 
@@ -38,14 +83,8 @@ impl CreateAccount {
         author_name: Option<String>,
         author_url: Option<String>,
     ) -> Self {
-        let author_name = match author_name {
-            Some(name) => Some(AuthorName::new(name)),
-            None => None
-        };
-        let author_url = match author_url {
-            Some(url) => Some(AuthorUrl::new(url)),
-            None => None
-        };
+        let author_name = author_name.map(AuthorName::new);
+        let author_url = author_url.map(AuthorUrl::new);
         Self {
             short_name: ShortName::new(short_name),
             author_name,
@@ -53,9 +92,35 @@ impl CreateAccount {
         }
     }
 
-    pub async fn run(&mut self, f: Box<dyn for<'a> Fn(&'a mut CreateAccount) -> Pin<Box<dyn Future<Output = Ret<Account>> + 'a>>> ) -> Ret<Account> {
+    pub async fn run(&mut self, f: Runner<CreateAccount, Account>) -> Ret<Account> {
         f(self).await
     }
+
+    /// Send the request through `transport` and decode the Account returned.
+    pub async fn send<T: Transport + ?Sized>(&self, transport: &T) -> Ret<Account> {
+        transport::execute(transport, self).await
+    }
+
+    /// Same as `send`, through a blocking transport.
+    pub fn send_blocking<T: BlockingTransport + ?Sized>(&self, transport: &T) -> Ret<Account> {
+        transport::execute_blocking(transport, self)
+    }
+}
+
+impl TelegraphMethod for CreateAccount {
+    type Response = Account;
+    const METHOD: &'static str = "createAccount";
+
+    fn params(&self) -> Params {
+        let mut params: Params = vec![("short_name", self.short_name.0.clone())];
+        if let Some(ref name) = self.author_name {
+            params.push(("author_name", name.0.clone()));
+        }
+        if let Some(ref url) = self.author_url {
+            params.push(("author_url", url.0.clone()));
+        }
+        params
+    }
 }
 
 /// Use this method to update information about a Telegraph account. Pass only the parameters that
@@ -72,16 +137,17 @@ impl CreateAccount {
 ///
 /// - Sample request
 ///     <https://api.telegra.ph/editAccountInfo?access_token=b968da509bb76866c35425099bc0989a5ec3b32997d55286c657e6994bbb&short_name=Sandbox&author_name=Anonymous>
+#[derive(Deserialize, Serialize)]
 pub struct EditAccountInfo {
     pub access_token: String,
     pub short_name: ShortName,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub author_name: Option<AuthorName>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub author_url: Option<AuthorUrl>,
 }
-use std::pin::Pin;
-use std::future::Future;
-pub type Ret<T> = Result<T, Box<dyn std::error::Error>> ;
-
 impl EditAccountInfo {
     pub fn new(access_token: String, short_name: String) -> Self {
         Self {
@@ -98,14 +164,8 @@ impl EditAccountInfo {
         author_name: Option<String>,
         author_url: Option<String>,
     ) -> Self {
-        let author_name = match author_name {
-            Some(name) => Some(AuthorName(name)),
-            None => None
-        };
-        let author_url = match author_url {
-            Some(url) => Some(AuthorUrl(url)),
-            None => None
-        };
+        let author_name = author_name.map(AuthorName);
+        let author_url = author_url.map(AuthorUrl);
         Self {
             access_token,
             short_name: ShortName::new(short_name),
@@ -114,9 +174,38 @@ impl EditAccountInfo {
         }
     }
 
-    pub async fn run(&mut self, f: Box<dyn for<'a> Fn(&'a mut EditAccountInfo) -> Pin<Box<dyn Future<Output = Ret<Account>> + 'a>>> ) -> Ret<Account> {
+    pub async fn run(&mut self, f: Runner<EditAccountInfo, Account>) -> Ret<Account> {
         f(self).await
     }
+
+    /// Send the request through `transport` and decode the Account returned.
+    pub async fn send<T: Transport + ?Sized>(&self, transport: &T) -> Ret<Account> {
+        transport::execute(transport, self).await
+    }
+
+    /// Same as `send`, through a blocking transport.
+    pub fn send_blocking<T: BlockingTransport + ?Sized>(&self, transport: &T) -> Ret<Account> {
+        transport::execute_blocking(transport, self)
+    }
+}
+
+impl TelegraphMethod for EditAccountInfo {
+    type Response = Account;
+    const METHOD: &'static str = "editAccountInfo";
+
+    fn params(&self) -> Params {
+        let mut params: Params = vec![
+            ("access_token", self.access_token.clone()),
+            ("short_name", self.short_name.0.clone()),
+        ];
+        if let Some(ref name) = self.author_name {
+            params.push(("author_name", name.0.clone()));
+        }
+        if let Some(ref url) = self.author_url {
+            params.push(("author_url", url.0.clone()));
+        }
+        params
+    }
 }
 
 /// Use this method to get information about a Telegraph account. Returns an Account object on
@@ -128,8 +217,11 @@ impl EditAccountInfo {
 ///
 /// - Sample request
 ///     <https://api.telegra.ph/getAccountInfo?access_token=b968da509bb76866c35425099bc0989a5ec3b32997d55286c657e6994bbb&fields=["short_name","page_count"]>
+#[derive(Deserialize, Serialize)]
 pub struct GetAccountInfo {
     pub access_token: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_default")]
     pub fields: Fields,
 }
 
@@ -141,9 +233,31 @@ impl GetAccountInfo {
         }
     }
 
-    pub async fn run(&mut self, f: Box<dyn for<'a> Fn(&'a mut GetAccountInfo) -> Pin<Box<dyn Future<Output = Ret<Account>> + 'a>>> ) -> Ret<Account> {
+    pub async fn run(&mut self, f: Runner<GetAccountInfo, Account>) -> Ret<Account> {
         f(self).await
     }
+
+    /// Send the request through `transport` and decode the Account returned.
+    pub async fn send<T: Transport + ?Sized>(&self, transport: &T) -> Ret<Account> {
+        transport::execute(transport, self).await
+    }
+
+    /// Same as `send`, through a blocking transport.
+    pub fn send_blocking<T: BlockingTransport + ?Sized>(&self, transport: &T) -> Ret<Account> {
+        transport::execute_blocking(transport, self)
+    }
+}
+
+impl TelegraphMethod for GetAccountInfo {
+    type Response = Account;
+    const METHOD: &'static str = "getAccountInfo";
+
+    fn params(&self) -> Params {
+        vec![
+            ("access_token", self.access_token.clone()),
+            ("fields", transport::to_json(&self.fields)),
+        ]
+    }
 }
 
 /// Use this method to revoke access_token and generate a new one, for example, if the user would
@@ -155,6 +269,7 @@ impl GetAccountInfo {
 ///
 /// - Sample request
 ///     <https://api.telegra.ph/revokeAccessToken?access_token=b968da509bb76866c35425099bc0989a5ec3b32997d55286c657e6994bbb>
+#[derive(Deserialize, Serialize)]
 pub struct RevokeAccessToken {
     pub access_token: String,
 }
@@ -166,9 +281,28 @@ impl RevokeAccessToken {
         }
     }
 
-    pub async fn run(&mut self, f: Box<dyn for<'a> Fn(&'a mut RevokeAccessToken) -> Pin<Box<dyn Future<Output = Ret<Account>> + 'a>>> ) -> Ret<Account> {
+    pub async fn run(&mut self, f: Runner<RevokeAccessToken, Account>) -> Ret<Account> {
         f(self).await
     }
+
+    /// Send the request through `transport` and decode the Account returned.
+    pub async fn send<T: Transport + ?Sized>(&self, transport: &T) -> Ret<Account> {
+        transport::execute(transport, self).await
+    }
+
+    /// Same as `send`, through a blocking transport.
+    pub fn send_blocking<T: BlockingTransport + ?Sized>(&self, transport: &T) -> Ret<Account> {
+        transport::execute_blocking(transport, self)
+    }
+}
+
+impl TelegraphMethod for RevokeAccessToken {
+    type Response = Account;
+    const METHOD: &'static str = "revokeAccessToken";
+
+    fn params(&self) -> Params {
+        vec![("access_token", self.access_token.clone())]
+    }
 }
 
 
@@ -189,30 +323,87 @@ impl RevokeAccessToken {
 ///
 /// - Sample request
 ///     <https://api.telegra.ph/createPage?access_token=b968da509bb76866c35425099bc0989a5ec3b32997d55286c657e6994bbb&title=Sample+Page&author_name=Anonymous&content=[{"tag":"p","children":["Hello,+world!"]}]&return_content=true>
+#[derive(Deserialize, Serialize)]
 pub struct CreatePage {
     pub access_token: String,
     pub title: Title,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_default")]
     pub author_name: AuthorName,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_default")]
     pub author_url: AuthorUrl,
     pub content: Content,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_default")]
     pub return_content: bool,
 }
 
 impl CreatePage {
+    /// Panics if the title is invalid, the content is sent as is, see `try_new` to validate it.
     pub fn new(access_token: String, title: String, content: Content, return_content: bool) -> Self {
         Self {
             access_token,
             title: Title::new(title),
-            author_name: AuthorName::new("".into()),
-            author_url: AuthorUrl::new("".into()),
+            author_name: AuthorName::default(),
+            author_url: AuthorUrl::default(),
             content,
             return_content,
         }
     }
 
-    pub async fn run(&mut self, f: Box<dyn for<'a> Fn(&'a mut CreatePage) -> Pin<Box<dyn Future<Output = Ret<Page>> + 'a>>> ) -> Ret<Page> {
+    /// Same as `new`, checking the title and the size of the content and running
+    /// `Content::validate` before building the request.
+    pub fn try_new(access_token: String, title: String, content: Content, return_content: bool) -> Result<Self, ValidationError> {
+        let title = Title::try_new(title)?;
+        content.check_size()?;
+        content.check()?;
+        Ok(Self {
+            access_token,
+            title,
+            author_name: AuthorName::default(),
+            author_url: AuthorUrl::default(),
+            content,
+            return_content,
+        })
+    }
+
+    pub async fn run(&mut self, f: Runner<CreatePage, Page>) -> Ret<Page> {
         f(self).await
     }
+
+    /// Send the request through `transport` and decode the Page returned.
+    pub async fn send<T: Transport + ?Sized>(&self, transport: &T) -> Ret<Page> {
+        transport::execute(transport, self).await
+    }
+
+    /// Same as `send`, through a blocking transport.
+    pub fn send_blocking<T: BlockingTransport + ?Sized>(&self, transport: &T) -> Ret<Page> {
+        transport::execute_blocking(transport, self)
+    }
+}
+
+impl TelegraphMethod for CreatePage {
+    type Response = Page;
+    const METHOD: &'static str = "createPage";
+
+    fn params(&self) -> Params {
+        let mut params: Params = vec![
+            ("access_token", self.access_token.clone()),
+            ("title", self.title.0.clone()),
+        ];
+        if !is_default(&self.author_name) {
+            params.push(("author_name", self.author_name.0.clone()));
+        }
+        if !is_default(&self.author_url) {
+            params.push(("author_url", self.author_url.0.clone()));
+        }
+        params.push(("content", transport::to_json(&self.content)));
+        if self.return_content {
+            params.push(("return_content", "true".into()));
+        }
+        params
+    }
 }
 
 /// Use this method to edit an existing Telegraph page. On success, returns a Page object.
@@ -234,45 +425,110 @@ impl CreatePage {
 ///
 /// - Sample request
 ///     <https://api.telegra.ph/editPage/Sample-Page-12-15?access_token=b968da509bb76866c35425099bc0989a5ec3b32997d55286c657e6994bbb&title=Sample+Page&author_name=Anonymous&content=[{"tag":"p","children":["Hello,+world!"]}]&return_content=true>
+#[derive(Deserialize, Serialize)]
 pub struct EditPage {
     pub access_token: String,
     pub path: String,
     pub title: Title,
     pub content: Content,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_default")]
     pub author_name: AuthorName,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_default")]
     pub author_url: AuthorUrl,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_default")]
     pub return_content: bool,
 }
 
 impl EditPage {
+    /// Panics if the title is invalid, the content is sent as is, see `try_new` to validate it.
     pub fn new(access_token: String, path: String, title: String, content: Content, return_content: bool) -> Self {
         Self {
             access_token,
             title: Title::new(title),
             path,
-            author_name: AuthorName::new("".into()),
-            author_url: AuthorUrl::new("".into()),
+            author_name: AuthorName::default(),
+            author_url: AuthorUrl::default(),
             content,
             return_content,
         }
     }
 
-    pub async fn run(&mut self, f: Box<dyn for<'a> Fn(&'a mut EditPage) -> Pin<Box<dyn Future<Output = Ret<Page>> + 'a>>> ) -> Ret<Page> {
+    /// Same as `new`, checking the title and the size of the content and running
+    /// `Content::validate` before building the request.
+    pub fn try_new(access_token: String, path: String, title: String, content: Content, return_content: bool) -> Result<Self, ValidationError> {
+        let title = Title::try_new(title)?;
+        content.check_size()?;
+        content.check()?;
+        Ok(Self {
+            access_token,
+            title,
+            path,
+            author_name: AuthorName::default(),
+            author_url: AuthorUrl::default(),
+            content,
+            return_content,
+        })
+    }
+
+    pub async fn run(&mut self, f: Runner<EditPage, Page>) -> Ret<Page> {
         f(self).await
     }
+
+    /// Send the request through `transport` and decode the Page returned.
+    pub async fn send<T: Transport + ?Sized>(&self, transport: &T) -> Ret<Page> {
+        transport::execute(transport, self).await
+    }
+
+    /// Same as `send`, through a blocking transport.
+    pub fn send_blocking<T: BlockingTransport + ?Sized>(&self, transport: &T) -> Ret<Page> {
+        transport::execute_blocking(transport, self)
+    }
+}
+
+impl TelegraphMethod for EditPage {
+    type Response = Page;
+    const METHOD: &'static str = "editPage";
+
+    fn path(&self) -> Option<&str> {
+        Some(&self.path)
+    }
+
+    fn params(&self) -> Params {
+        let mut params: Params = vec![
+            ("access_token", self.access_token.clone()),
+            ("title", self.title.0.clone()),
+        ];
+        if !is_default(&self.author_name) {
+            params.push(("author_name", self.author_name.0.clone()));
+        }
+        if !is_default(&self.author_url) {
+            params.push(("author_url", self.author_url.0.clone()));
+        }
+        params.push(("content", transport::to_json(&self.content)));
+        if self.return_content {
+            params.push(("return_content", "true".into()));
+        }
+        params
+    }
 }
 
 /// Use this method to get a Telegraph page. Returns a Page object on success.
 /// 
 /// - path (String)
-///    Required. Path to the Telegraph page (in the format Title-12-31, i.e. everything that comes after <http://telegra.ph/>).
+///     Required. Path to the Telegraph page (in the format Title-12-31, i.e. everything that comes after <http://telegra.ph/>).
 /// - return_content (Boolean, default = false)
-///    If true, content field will be returned in Page object.
+///     If true, content field will be returned in Page object.
 ///
 /// - Sample request
-///    <https://api.telegra.ph/getPage/Sample-Page-12-15?return_content=true>
+///     <https://api.telegra.ph/getPage/Sample-Page-12-15?return_content=true>
+#[derive(Deserialize, Serialize)]
 pub struct GetPage {
     pub path: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_default")]
     pub return_content: bool,
 }
 
@@ -284,9 +540,36 @@ impl GetPage {
         }
     }
 
-    pub async fn run(&mut self, f: Box<dyn for<'a> Fn(&'a mut GetPage) -> Pin<Box<dyn Future<Output = Ret<Page>> + 'a>>> ) -> Ret<Page> {
+    pub async fn run(&mut self, f: Runner<GetPage, Page>) -> Ret<Page> {
         f(self).await
     }
+
+    /// Send the request through `transport` and decode the Page returned.
+    pub async fn send<T: Transport + ?Sized>(&self, transport: &T) -> Ret<Page> {
+        transport::execute(transport, self).await
+    }
+
+    /// Same as `send`, through a blocking transport.
+    pub fn send_blocking<T: BlockingTransport + ?Sized>(&self, transport: &T) -> Ret<Page> {
+        transport::execute_blocking(transport, self)
+    }
+}
+
+impl TelegraphMethod for GetPage {
+    type Response = Page;
+    const METHOD: &'static str = "getPage";
+
+    fn path(&self) -> Option<&str> {
+        Some(&self.path)
+    }
+
+    fn params(&self) -> Params {
+        let mut params: Params = vec![];
+        if self.return_content {
+            params.push(("return_content", "true".into()));
+        }
+        params
+    }
 }
 
 /// Use this method to get a list of pages belonging to a Telegraph account. Returns a PageList object, sorted by most recently created pages first.
@@ -300,9 +583,14 @@ impl GetPage {
 ///
 /// - Sample request
 ///     <https://api.telegra.ph/getPageList?access_token=b968da509bb76866c35425099bc0989a5ec3b32997d55286c657e6994bbb&limit=3>
+#[derive(Deserialize, Serialize)]
 pub struct GetPageList {
     pub access_token: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_default")]
     pub offset: u32,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_default")]
     pub limit: Limit,
 }
 
@@ -315,9 +603,35 @@ impl GetPageList {
         }
     }
 
-    pub async fn run(&mut self, f: Box<dyn for<'a> Fn(&'a mut GetPageList) -> Pin<Box<dyn Future<Output = Ret<PageList>> + 'a>>> ) -> Ret<PageList> {
+    pub async fn run(&mut self, f: Runner<GetPageList, PageList>) -> Ret<PageList> {
         f(self).await
     }
+
+    /// Send the request through `transport` and decode the PageList returned.
+    pub async fn send<T: Transport + ?Sized>(&self, transport: &T) -> Ret<PageList> {
+        transport::execute(transport, self).await
+    }
+
+    /// Same as `send`, through a blocking transport.
+    pub fn send_blocking<T: BlockingTransport + ?Sized>(&self, transport: &T) -> Ret<PageList> {
+        transport::execute_blocking(transport, self)
+    }
+}
+
+impl TelegraphMethod for GetPageList {
+    type Response = PageList;
+    const METHOD: &'static str = "getPageList";
+
+    fn params(&self) -> Params {
+        let mut params: Params = vec![("access_token", self.access_token.clone())];
+        if self.offset != 0 {
+            params.push(("offset", self.offset.to_string()));
+        }
+        if !is_default(&self.limit) {
+            params.push(("limit", self.limit.0.to_string()));
+        }
+        params
+    }
 }
 
 /// Use this method to get the number of views for a Telegraph article. Returns a PageViews object on success. By default, the total number of page views will be returned.
@@ -335,11 +649,20 @@ impl GetPageList {
 ///
 /// - Sample request
 ///     <https://api.telegra.ph/getViews/Sample-Page-12-15?year=2016&month=12>
+#[derive(Deserialize, Serialize)]
 pub struct GetViews {
     pub path: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub year: Option<Year>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub month: Option<Month>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub day: Option<Day>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub hour: Option<Hour>,
 }
 
@@ -354,8 +677,44 @@ impl GetViews {
         }
     }
 
-    pub async fn run(&mut self, f: Box<dyn for<'a> Fn(&'a mut GetViews) -> Pin<Box<dyn Future<Output = Ret<PageViews>> + 'a>>> ) -> Ret<PageViews> {
+    pub async fn run(&mut self, f: Runner<GetViews, PageViews>) -> Ret<PageViews> {
         f(self).await
     }
+
+    /// Send the request through `transport` and decode the PageViews returned.
+    pub async fn send<T: Transport + ?Sized>(&self, transport: &T) -> Ret<PageViews> {
+        transport::execute(transport, self).await
+    }
+
+    /// Same as `send`, through a blocking transport.
+    pub fn send_blocking<T: BlockingTransport + ?Sized>(&self, transport: &T) -> Ret<PageViews> {
+        transport::execute_blocking(transport, self)
+    }
+}
+
+impl TelegraphMethod for GetViews {
+    type Response = PageViews;
+    const METHOD: &'static str = "getViews";
+
+    fn path(&self) -> Option<&str> {
+        Some(&self.path)
+    }
+
+    fn params(&self) -> Params {
+        let mut params: Params = vec![];
+        if let Some(ref year) = self.year {
+            params.push(("year", year.0.to_string()));
+        }
+        if let Some(ref month) = self.month {
+            params.push(("month", month.0.to_string()));
+        }
+        if let Some(ref day) = self.day {
+            params.push(("day", day.0.to_string()));
+        }
+        if let Some(ref hour) = self.hour {
+            params.push(("hour", hour.0.to_string()));
+        }
+        params
+    }
 }
 
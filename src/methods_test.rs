@@ -2,8 +2,6 @@
 mod test_create_account {
     use crate::methods::*;
     use crate::entity::*;
-    use std::pin::Pin;
-    use std::future::Future ;
     use futures::executor::block_on;
 
     #[test]
@@ -21,16 +19,16 @@ mod test_create_account {
         account_.author_name = Some(AuthorName::new("captain".into()));
         assert_eq!(account.author_name.as_ref().unwrap().0, account_.author_name.as_ref().unwrap().0);
 
-        let f: Box<dyn for<'a> Fn(&'a mut CreateAccount) -> Pin<Box<dyn Future<Output = Ret<Account>> + 'a>>> = Box::new(move |_accnt: &mut CreateAccount| Box::pin(async move { Ok(Account::new("captain".into())) }));
+        let f: Runner<CreateAccount, Account> = Box::new(move |_accnt: &mut CreateAccount| Box::pin(async move { Ok(Account::new("captain".into())) }));
         let res = block_on(account.run(f));
         assert!(res.is_ok());
         let res = res.unwrap();
-        assert_eq!(res.short_name.0, "captain");
-        let f: Box<dyn for<'a> Fn(&'a mut CreateAccount) -> Pin<Box<dyn Future<Output = Ret<Account>> + 'a>>> = Box::new(move |_accnt: &mut CreateAccount| Box::pin( futures::future::ready(Ok(Account::new("captain".into()))) ));
+        assert_eq!(res.short_name.as_ref().unwrap().0, "captain");
+        let f: Runner<CreateAccount, Account> = Box::new(move |_accnt: &mut CreateAccount| Box::pin( futures::future::ready(Ok(Account::new("captain".into()))) ));
         let res = block_on(account.run(f));
         assert!(res.is_ok());
         let res = res.unwrap();
-        assert_eq!(res.short_name.0, "captain");
+        assert_eq!(res.short_name.as_ref().unwrap().0, "captain");
     }
 }
 
@@ -38,8 +36,6 @@ mod test_create_account {
 mod test_edit_account_info {
     use crate::methods::*;
     use crate::entity::*;
-    use std::pin::Pin;
-    use std::future::Future ;
     use futures::executor::block_on;
 
     #[test]
@@ -58,16 +54,16 @@ mod test_edit_account_info {
         account_.author_name = Some(AuthorName::new("captain".into()));
         assert_eq!(account.author_name.as_ref().unwrap().0, account_.author_name.as_ref().unwrap().0);
 
-        let f: Box<dyn for<'a> Fn(&'a mut EditAccountInfo) -> Pin<Box<dyn Future<Output = Ret<Account>> + 'a>>> = Box::new(move |_accnt: &mut EditAccountInfo| Box::pin(async move { Ok(Account::new("captain".into())) }));
+        let f: Runner<EditAccountInfo, Account> = Box::new(move |_accnt: &mut EditAccountInfo| Box::pin(async move { Ok(Account::new("captain".into())) }));
         let res = block_on(account.run(f));
         assert!(res.is_ok());
         let res = res.unwrap();
-        assert_eq!(res.short_name.0, "captain");
-        let f: Box<dyn for<'a> Fn(&'a mut EditAccountInfo) -> Pin<Box<dyn Future<Output = Ret<Account>> + 'a>>> = Box::new(move |_accnt: &mut EditAccountInfo| Box::pin( futures::future::ready(Ok(Account::new("captain".into()))) ));
+        assert_eq!(res.short_name.as_ref().unwrap().0, "captain");
+        let f: Runner<EditAccountInfo, Account> = Box::new(move |_accnt: &mut EditAccountInfo| Box::pin( futures::future::ready(Ok(Account::new("captain".into()))) ));
         let res = block_on(account.run(f));
         assert!(res.is_ok());
         let res = res.unwrap();
-        assert_eq!(res.short_name.0, "captain");
+        assert_eq!(res.short_name.as_ref().unwrap().0, "captain");
     }
 }
 
@@ -75,8 +71,6 @@ mod test_edit_account_info {
 mod test_get_account_info {
     use crate::methods::*;
     use crate::entity::*;
-    use std::pin::Pin;
-    use std::future::Future ;
     use futures::executor::block_on;
 
     #[test]
@@ -90,16 +84,16 @@ mod test_get_account_info {
         assert!(account.fields.0.contains(&"short_name".into()));
         assert!(account.fields.0.contains(&"auth_url".into()));
 
-        let f: Box<dyn for<'a> Fn(&'a mut GetAccountInfo) -> Pin<Box<dyn Future<Output = Ret<Account>> + 'a>>> = Box::new(move |_accnt: &mut GetAccountInfo| Box::pin(async move { Ok(Account::new("captain".into())) }));
+        let f: Runner<GetAccountInfo, Account> = Box::new(move |_accnt: &mut GetAccountInfo| Box::pin(async move { Ok(Account::new("captain".into())) }));
         let res = block_on(account.run(f));
         assert!(res.is_ok());
         let res = res.unwrap();
-        assert_eq!(res.short_name.0, "captain");
-        let f: Box<dyn for<'a> Fn(&'a mut GetAccountInfo) -> Pin<Box<dyn Future<Output = Ret<Account>> + 'a>>> = Box::new(move |_accnt: &mut GetAccountInfo| Box::pin( futures::future::ready(Ok(Account::new("captain".into()))) ));
+        assert_eq!(res.short_name.as_ref().unwrap().0, "captain");
+        let f: Runner<GetAccountInfo, Account> = Box::new(move |_accnt: &mut GetAccountInfo| Box::pin( futures::future::ready(Ok(Account::new("captain".into()))) ));
         let res = block_on(account.run(f));
         assert!(res.is_ok());
         let res = res.unwrap();
-        assert_eq!(res.short_name.0, "captain");
+        assert_eq!(res.short_name.as_ref().unwrap().0, "captain");
     }
 }
 
@@ -107,8 +101,6 @@ mod test_get_account_info {
 mod test_revoke_access_token {
     use crate::methods::*;
     use crate::entity::*;
-    use std::pin::Pin;
-    use std::future::Future ;
     use futures::executor::block_on;
 
     #[test]
@@ -116,16 +108,16 @@ mod test_revoke_access_token {
         let mut account = RevokeAccessToken::new("1234567".into());
         assert_eq!(account.access_token, "1234567".to_owned());
 
-        let f: Box<dyn for<'a> Fn(&'a mut RevokeAccessToken) -> Pin<Box<dyn Future<Output = Ret<Account>> + 'a>>> = Box::new(move |_accnt: &mut RevokeAccessToken| Box::pin(async move { Ok(Account::new("captain".into())) }));
+        let f: Runner<RevokeAccessToken, Account> = Box::new(move |_accnt: &mut RevokeAccessToken| Box::pin(async move { Ok(Account::new("captain".into())) }));
         let res = block_on(account.run(f));
         assert!(res.is_ok());
         let res = res.unwrap();
-        assert_eq!(res.short_name.0, "captain");
-        let f: Box<dyn for<'a> Fn(&'a mut RevokeAccessToken) -> Pin<Box<dyn Future<Output = Ret<Account>> + 'a>>> = Box::new(move |_accnt: &mut RevokeAccessToken| Box::pin( futures::future::ready(Ok(Account::new("captain".into()))) ));
+        assert_eq!(res.short_name.as_ref().unwrap().0, "captain");
+        let f: Runner<RevokeAccessToken, Account> = Box::new(move |_accnt: &mut RevokeAccessToken| Box::pin( futures::future::ready(Ok(Account::new("captain".into()))) ));
         let res = block_on(account.run(f));
         assert!(res.is_ok());
         let res = res.unwrap();
-        assert_eq!(res.short_name.0, "captain");
+        assert_eq!(res.short_name.as_ref().unwrap().0, "captain");
     }
 }
 
@@ -133,8 +125,6 @@ mod test_revoke_access_token {
 mod test_create_page {
     use crate::methods::*;
     use crate::entity::*;
-    use std::pin::Pin;
-    use std::future::Future ;
     use futures::executor::block_on;
 
     #[test]
@@ -142,7 +132,7 @@ mod test_create_page {
         let mut account = CreatePage::new("1234567".into(),"title".into(), Content::new(vec![Node::String("text node".into())]), false);
         assert_eq!(account.access_token, "1234567".to_owned());
         assert_eq!(account.title.0, "title".to_owned());
-        assert_eq!(account.return_content, false);
+        assert!(!account.return_content);
         account.content.0.push(Node::String("text node 2".to_owned()));
         let item = &account.content.0;
         assert_eq!(item.len(), 2);
@@ -150,7 +140,7 @@ mod test_create_page {
             assert_eq!(s, "text node");
         }
 
-        let f: Box<dyn for<'a> Fn(&'a mut CreatePage) -> Pin<Box<dyn Future<Output = Ret<Page>> + 'a>>> = Box::new(move |_accnt: &mut CreatePage| Box::pin(async move { Ok(Page::new("path".into(), "https://example.com/".into(), "title".into(), "description".into(), vec![Node::String("text node".into())])) }));
+        let f: Runner<CreatePage, Page> = Box::new(move |_accnt: &mut CreatePage| Box::pin(async move { Ok(Page::new("path".into(), "https://example.com/".into(), "title".into(), "description".into(), vec![Node::String("text node".into())])) }));
         let res = block_on(account.run(f));
         assert!(res.is_ok());
         let res = res.unwrap();
@@ -164,7 +154,7 @@ mod test_create_page {
         assert!(res.image_url.is_none());
         assert!(res.content.as_ref().unwrap().len() == 1);
         assert!(res.can_edit.is_none());
-        let f: Box<dyn for<'a> Fn(&'a mut CreatePage) -> Pin<Box<dyn Future<Output = Ret<Page>> + 'a>>> = Box::new(move |_accnt: &mut CreatePage| Box::pin( futures::future::ready(Ok(Page::new("path".into(), "https://example.com/".into(), "title".into(), "description".into(), vec![Node::String("text node".into())]))) ));
+        let f: Runner<CreatePage, Page> = Box::new(move |_accnt: &mut CreatePage| Box::pin( futures::future::ready(Ok(Page::new("path".into(), "https://example.com/".into(), "title".into(), "description".into(), vec![Node::String("text node".into())]))) ));
         let res = block_on(account.run(f));
         assert!(res.is_ok());
         let res = res.unwrap();
@@ -185,8 +175,6 @@ mod test_create_page {
 mod test_edit_page {
     use crate::methods::*;
     use crate::entity::*;
-    use std::pin::Pin;
-    use std::future::Future ;
     use futures::executor::block_on;
 
     #[test]
@@ -195,7 +183,7 @@ mod test_edit_page {
         assert_eq!(account.access_token, "1234567".to_owned());
         assert_eq!(account.path, "path".to_owned());
         assert_eq!(account.title.0, "title".to_owned());
-        assert_eq!(account.return_content, false);
+        assert!(!account.return_content);
         account.content.0.push(Node::String("text node 2".to_owned()));
         let item = &account.content.0;
         assert_eq!(item.len(), 2);
@@ -203,7 +191,7 @@ mod test_edit_page {
             assert_eq!(s, "text node");
         }
 
-        let f: Box<dyn for<'a> Fn(&'a mut EditPage) -> Pin<Box<dyn Future<Output = Ret<Page>> + 'a>>> = Box::new(move |_accnt: &mut EditPage| Box::pin(async move { Ok(Page::new("path".into(), "https://example.com/".into(), "title".into(), "description".into(), vec![Node::String("text node".into())])) }));
+        let f: Runner<EditPage, Page> = Box::new(move |_accnt: &mut EditPage| Box::pin(async move { Ok(Page::new("path".into(), "https://example.com/".into(), "title".into(), "description".into(), vec![Node::String("text node".into())])) }));
         let res = block_on(account.run(f));
         assert!(res.is_ok());
         let res = res.unwrap();
@@ -217,7 +205,7 @@ mod test_edit_page {
         assert!(res.image_url.is_none());
         assert!(res.content.as_ref().unwrap().len() == 1);
         assert!(res.can_edit.is_none());
-        let f: Box<dyn for<'a> Fn(&'a mut EditPage) -> Pin<Box<dyn Future<Output = Ret<Page>> + 'a>>> = Box::new(move |_accnt: &mut EditPage| Box::pin( futures::future::ready(Ok(Page::new("path".into(), "https://example.com/".into(), "title".into(), "description".into(), vec![Node::String("text node".into())]))) ));
+        let f: Runner<EditPage, Page> = Box::new(move |_accnt: &mut EditPage| Box::pin( futures::future::ready(Ok(Page::new("path".into(), "https://example.com/".into(), "title".into(), "description".into(), vec![Node::String("text node".into())]))) ));
         let res = block_on(account.run(f));
         assert!(res.is_ok());
         let res = res.unwrap();
@@ -238,17 +226,15 @@ mod test_edit_page {
 mod test_get_page {
     use crate::methods::*;
     use crate::entity::*;
-    use std::pin::Pin;
-    use std::future::Future ;
     use futures::executor::block_on;
 
     #[test]
     fn test_normal() {
         let mut account = GetPage::new( "path".into(),  false);
         assert_eq!(account.path, "path".to_owned());
-        assert_eq!(account.return_content, false);
+        assert!(!account.return_content);
 
-        let f: Box<dyn for<'a> Fn(&'a mut GetPage) -> Pin<Box<dyn Future<Output = Ret<Page>> + 'a>>> = Box::new(move |_accnt: &mut GetPage| Box::pin(async move { Ok(Page::new("path".into(), "https://example.com/".into(), "title".into(), "description".into(), vec![Node::String("text node".into())])) }));
+        let f: Runner<GetPage, Page> = Box::new(move |_accnt: &mut GetPage| Box::pin(async move { Ok(Page::new("path".into(), "https://example.com/".into(), "title".into(), "description".into(), vec![Node::String("text node".into())])) }));
         let res = block_on(account.run(f));
         assert!(res.is_ok());
         let res = res.unwrap();
@@ -262,7 +248,7 @@ mod test_get_page {
         assert!(res.image_url.is_none());
         assert!(res.content.as_ref().unwrap().len() == 1);
         assert!(res.can_edit.is_none());
-        let f: Box<dyn for<'a> Fn(&'a mut GetPage) -> Pin<Box<dyn Future<Output = Ret<Page>> + 'a>>> = Box::new(move |_accnt: &mut GetPage| Box::pin( futures::future::ready(Ok(Page::new("path".into(), "https://example.com/".into(), "title".into(), "description".into(), vec![Node::String("text node".into())]))) ));
+        let f: Runner<GetPage, Page> = Box::new(move |_accnt: &mut GetPage| Box::pin( futures::future::ready(Ok(Page::new("path".into(), "https://example.com/".into(), "title".into(), "description".into(), vec![Node::String("text node".into())]))) ));
         let res = block_on(account.run(f));
         assert!(res.is_ok());
         let res = res.unwrap();
@@ -283,8 +269,6 @@ mod test_get_page {
 mod test_get_page_list {
     use crate::methods::*;
     use crate::entity::*;
-    use std::pin::Pin;
-    use std::future::Future ;
     use futures::executor::block_on;
 
     #[test]
@@ -293,14 +277,14 @@ mod test_get_page_list {
         assert_eq!(account.access_token, "1234567".to_owned());
         assert_eq!(account.offset, 0);
 
-        let f: Box<dyn for<'a> Fn(&'a mut GetPageList) -> Pin<Box<dyn Future<Output = Ret<PageList>> + 'a>>> = Box::new(move |_accnt: &mut GetPageList| Box::pin(async move { Ok(PageList::new()) }));
+        let f: Runner<GetPageList, PageList> = Box::new(move |_accnt: &mut GetPageList| Box::pin(async move { Ok(PageList::new()) }));
         let res = block_on(account.run(f));
         assert!(res.is_ok());
         let res = res.unwrap();
         assert_eq!(res.total_count, 0);
         assert!(res.pages.is_empty());
 
-        let f: Box<dyn for<'a> Fn(&'a mut GetPageList) -> Pin<Box<dyn Future<Output = Ret<PageList>> + 'a>>> = Box::new(move |_accnt: &mut GetPageList| Box::pin( futures::future::ready(Ok(PageList::new())) ));
+        let f: Runner<GetPageList, PageList> = Box::new(move |_accnt: &mut GetPageList| Box::pin( futures::future::ready(Ok(PageList::new())) ));
         let res = block_on(account.run(f));
         assert!(res.is_ok());
         let res = res.unwrap();
@@ -313,8 +297,6 @@ mod test_get_page_list {
 mod test_get_views {
     use crate::methods::*;
     use crate::entity::*;
-    use std::pin::Pin;
-    use std::future::Future ;
     use futures::executor::block_on;
 
     #[test]
@@ -323,16 +305,339 @@ mod test_get_views {
         assert_eq!(account.path, "path".to_owned());
         assert_eq!(account.year.as_ref().unwrap().0, 2022);
 
-        let f: Box<dyn for<'a> Fn(&'a mut GetViews) -> Pin<Box<dyn Future<Output = Ret<PageViews>> + 'a>>> = Box::new(move |_accnt: &mut GetViews| Box::pin(async move { Ok(PageViews::new()) }));
+        let f: Runner<GetViews, PageViews> = Box::new(move |_accnt: &mut GetViews| Box::pin(async move { Ok(PageViews::new()) }));
         let res = block_on(account.run(f));
         assert!(res.is_ok());
         let res = res.unwrap();
         assert_eq!(res.views, 0);
 
-        let f: Box<dyn for<'a> Fn(&'a mut GetViews) -> Pin<Box<dyn Future<Output = Ret<PageViews>> + 'a>>> = Box::new(move |_accnt: &mut GetViews| Box::pin( futures::future::ready(Ok(PageViews::new())) ));
+        let f: Runner<GetViews, PageViews> = Box::new(move |_accnt: &mut GetViews| Box::pin( futures::future::ready(Ok(PageViews::new())) ));
         let res = block_on(account.run(f));
         assert!(res.is_ok());
         let res = res.unwrap();
         assert_eq!(res.views, 0);
     }
 }
+
+#[cfg(test)]
+mod test_send {
+    use crate::methods::*;
+    use crate::entity::*;
+    use crate::error::*;
+    use crate::request::*;
+    use crate::transport::*;
+    use std::pin::Pin;
+    use std::future::Future;
+    use std::sync::Mutex;
+    use futures::executor::block_on;
+
+    struct Mock {
+        body: &'static str,
+        calls: Mutex<Vec<Request>>,
+    }
+
+    impl Mock {
+        fn new(body: &'static str) -> Self {
+            Self { body, calls: Mutex::new(vec![]) }
+        }
+    }
+
+    impl Transport for Mock {
+        fn call<'a>(&'a self, request: Request) -> Pin<Box<dyn Future<Output = Ret<String>> + Send + 'a>> {
+            self.calls.lock().unwrap().push(request);
+            Box::pin(futures::future::ready(Ok(self.body.to_string())))
+        }
+    }
+
+    #[test]
+    fn test_account() {
+        let mock = Mock::new(r#"{"ok":true,"result":{"short_name":"Sandbox","author_name":"Anonymous","access_token":"abc"}}"#);
+        let mut account = CreateAccount::new("Sandbox".into());
+        account.author_name = Some(AuthorName::new("Anonymous".into()));
+        let res = block_on(account.send(&mock));
+        assert!(res.is_ok());
+        let res = res.unwrap();
+        assert_eq!(res.short_name.as_ref().unwrap().0, "Sandbox");
+        assert_eq!(res.access_token.as_ref().unwrap(), "abc");
+        let calls = mock.calls.lock().unwrap();
+        assert_eq!(calls[0].method, "createAccount");
+        assert!(calls[0].path.is_none());
+        assert_eq!(calls[0].params, vec![("short_name", "Sandbox".to_string()), ("author_name", "Anonymous".to_string())]);
+    }
+
+    #[test]
+    fn test_views() {
+        let mock = Mock::new(r#"{"ok":true,"result":{"views":40}}"#);
+        let mut views = GetViews::new("Sample-Page-12-15".into(), 2016);
+        views.month = Some(Month::new(12));
+        let res = block_on(views.send(&mock));
+        assert_eq!(res.unwrap().views, 40);
+        let calls = mock.calls.lock().unwrap();
+        assert_eq!(calls[0].method, "getViews");
+        assert_eq!(calls[0].path.as_deref(), Some("Sample-Page-12-15"));
+        assert_eq!(calls[0].params, vec![("year", "2016".to_string()), ("month", "12".to_string())]);
+    }
+
+    #[test]
+    fn test_error() {
+        let mock = Mock::new(r#"{"ok":false,"error":"ACCESS_TOKEN_INVALID"}"#);
+        let list = GetPageList::new("1234567".into(), 0);
+        let res = block_on(list.send(&mock));
+        let err = res.err().unwrap();
+        assert!(err.is_auth());
+        assert!(matches!(err, TelegraphError::Api(ApiError::AccessTokenInvalid)));
+        let calls = mock.calls.lock().unwrap();
+        assert_eq!(calls[0].method, "getPageList");
+        assert_eq!(calls[0].params, vec![("access_token", "1234567".to_string())]);
+    }
+}
+
+#[cfg(test)]
+mod test_send_bounds {
+    use crate::methods::*;
+    use crate::entity::*;
+    use crate::error::*;
+    use crate::request::*;
+    use crate::transport::*;
+    use std::pin::Pin;
+    use std::future::Future;
+
+    struct Mock;
+
+    impl Transport for Mock {
+        fn call<'a>(&'a self, _request: Request) -> Pin<Box<dyn Future<Output = Ret<String>> + Send + 'a>> {
+            Box::pin(futures::future::ready(Ok(r#"{"ok":true,"result":{"views":0}}"#.to_string())))
+        }
+    }
+
+    fn assert_send<T: Send>(_: &T) {}
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn test_futures() {
+        let mock = Mock;
+        let content = || Content::new(vec![Node::String("text node".into())]);
+        let mut create_account = CreateAccount::new("Sandbox".into());
+        let mut edit_account_info = EditAccountInfo::new("1234567".into(), "Sandbox".into());
+        let mut get_account_info = GetAccountInfo::new("1234567".into());
+        let mut revoke_access_token = RevokeAccessToken::new("1234567".into());
+        let mut create_page = CreatePage::new("1234567".into(), "title".into(), content(), false);
+        let mut edit_page = EditPage::new("1234567".into(), "path".into(), "title".into(), content(), false);
+        let mut get_page = GetPage::new("path".into(), false);
+        let mut get_page_list = GetPageList::new("1234567".into(), 0);
+        let mut get_views = GetViews::new("path".into(), 2016);
+
+        assert_send(&create_account.send(&mock));
+        assert_send(&edit_account_info.send(&mock));
+        assert_send(&get_account_info.send(&mock));
+        assert_send(&revoke_access_token.send(&mock));
+        assert_send(&create_page.send(&mock));
+        assert_send(&edit_page.send(&mock));
+        assert_send(&get_page.send(&mock));
+        assert_send(&get_page_list.send(&mock));
+        assert_send(&get_views.send(&mock));
+
+        assert_send(&create_account.run(Box::new(|_| Box::pin(async { Ok(Account::new("Sandbox".into())) }))));
+        assert_send(&edit_account_info.run(Box::new(|_| Box::pin(async { Ok(Account::new("Sandbox".into())) }))));
+        assert_send(&get_account_info.run(Box::new(|_| Box::pin(async { Ok(Account::new("Sandbox".into())) }))));
+        assert_send(&revoke_access_token.run(Box::new(|_| Box::pin(async { Ok(Account::new("Sandbox".into())) }))));
+        assert_send(&create_page.run(Box::new(|_| Box::pin(async { Err(ApiError::PageSaveFailed.into()) }))));
+        assert_send(&edit_page.run(Box::new(|_| Box::pin(async { Err(ApiError::PageSaveFailed.into()) }))));
+        assert_send(&get_page.run(Box::new(|_| Box::pin(async { Err(ApiError::PageNotFound.into()) }))));
+        assert_send(&get_page_list.run(Box::new(|_| Box::pin(async { Ok(PageList::new()) }))));
+        assert_send(&get_views.run(Box::new(|_| Box::pin(async { Ok(PageViews::new()) }))));
+    }
+
+    #[test]
+    fn test_types() {
+        assert_send_sync::<TelegraphError>();
+        assert_send_sync::<Request>();
+        assert_send_sync::<CreatePage>();
+        assert_send_sync::<Page>();
+        assert_send_sync::<Account>();
+    }
+
+    #[test]
+    fn test_spawn() {
+        let rt = tokio::runtime::Builder::new_multi_thread().worker_threads(2).build().unwrap();
+        let views = rt.block_on(async {
+            let handle = tokio::spawn(async {
+                GetViews::new("path".into(), 2016).send(&Mock).await
+            });
+            handle.await.unwrap()
+        });
+        assert_eq!(views.unwrap().views, 0);
+    }
+}
+
+#[cfg(test)]
+mod test_telegraph_method {
+    use crate::methods::*;
+    use crate::entity::*;
+    use crate::request::*;
+    use crate::transport::*;
+    use std::pin::Pin;
+    use std::future::Future;
+    use std::sync::Mutex;
+    use futures::executor::block_on;
+
+    /// Answers every call with the canned body registered for its method.
+    struct Mock {
+        bodies: Vec<(&'static str, &'static str)>,
+        calls: Mutex<Vec<&'static str>>,
+    }
+
+    impl Transport for Mock {
+        fn call<'a>(&'a self, request: Request) -> Pin<Box<dyn Future<Output = Ret<String>> + Send + 'a>> {
+            self.calls.lock().unwrap().push(request.method);
+            let body = self.bodies.iter().find(|(m, _)| *m == request.method).map(|(_, b)| b.to_string()).unwrap_or_default();
+            Box::pin(futures::future::ready(Ok(body)))
+        }
+    }
+
+    fn method_name<M: TelegraphMethod>(_: &M) -> &'static str {
+        M::METHOD
+    }
+
+    /// Retry wrapper written once for every method.
+    async fn with_retry<M, T>(transport: &T, method: &M, attempts: usize) -> Ret<M::Response>
+        where
+            M: TelegraphMethod + Sync,
+            T: Transport,
+    {
+        let mut res = execute(transport, method).await;
+        for _ in 1..attempts {
+            match res {
+                Err(ref e) if e.is_retryable() => res = execute(transport, method).await,
+                _ => break,
+            }
+        }
+        res
+    }
+
+    #[test]
+    fn test_names() {
+        let content = || Content::new(vec![Node::String("text node".into())]);
+        assert_eq!(method_name(&CreateAccount::new("Sandbox".into())), "createAccount");
+        assert_eq!(method_name(&EditAccountInfo::new("1234567".into(), "Sandbox".into())), "editAccountInfo");
+        assert_eq!(method_name(&GetAccountInfo::new("1234567".into())), "getAccountInfo");
+        assert_eq!(method_name(&RevokeAccessToken::new("1234567".into())), "revokeAccessToken");
+        assert_eq!(method_name(&CreatePage::new("1234567".into(), "title".into(), content(), false)), "createPage");
+        assert_eq!(method_name(&EditPage::new("1234567".into(), "path".into(), "title".into(), content(), false)), "editPage");
+        assert_eq!(method_name(&GetPage::new("path".into(), false)), "getPage");
+        assert_eq!(method_name(&GetPageList::new("1234567".into(), 0)), "getPageList");
+        assert_eq!(method_name(&GetViews::new("path".into(), 2016)), "getViews");
+    }
+
+    #[test]
+    fn test_path() {
+        let content = || Content::new(vec![Node::String("text node".into())]);
+        assert_eq!(CreateAccount::new("Sandbox".into()).path(), None);
+        assert_eq!(CreatePage::new("1234567".into(), "title".into(), content(), false).path(), None);
+        assert_eq!(EditPage::new("1234567".into(), "path".into(), "title".into(), content(), false).path(), Some("path"));
+        assert_eq!(GetPage::new("path".into(), false).path(), Some("path"));
+        assert_eq!(GetViews::new("path".into(), 2016).path(), Some("path"));
+        let req = GetViews::new("path".into(), 2016).to_request();
+        assert_eq!(req.method, "getViews");
+        assert_eq!(req.path.as_deref(), Some("path"));
+    }
+
+    #[test]
+    fn test_generic() {
+        let mock = Mock {
+            bodies: vec![
+                ("getViews", r#"{"ok":true,"result":{"views":7}}"#),
+                ("getPageList", r#"{"ok":true,"result":{"total_count":0,"pages":[]}}"#),
+                ("getPage", r#"{"ok":false,"error":"FLOOD_WAIT_1"}"#),
+            ],
+            calls: Mutex::new(vec![]),
+        };
+        let views = block_on(with_retry(&mock, &GetViews::new("path".into(), 2016), 3));
+        assert_eq!(views.unwrap().views, 7);
+        let list = block_on(with_retry(&mock, &GetPageList::new("1234567".into(), 0), 3));
+        assert_eq!(list.unwrap().total_count, 0);
+        let page = block_on(with_retry(&mock, &GetPage::new("path".into(), false), 3));
+        assert!(page.is_err());
+        assert_eq!(*mock.calls.lock().unwrap(), vec!["getViews", "getPageList", "getPage", "getPage", "getPage"]);
+    }
+}
+
+#[cfg(test)]
+mod test_serde {
+    use crate::methods::*;
+    use crate::entity::*;
+    use serde_json::{json, Value};
+
+    #[test]
+    fn test_account() {
+        let account = CreateAccount::new("Sandbox".into());
+        assert_eq!(serde_json::to_string(&account).unwrap(), r#"{"short_name":"Sandbox"}"#);
+        let account = CreateAccount::with_raw("Sandbox".into(), Some("Anonymous".into()), Some("https://t.me/".into()));
+        let ser = serde_json::to_string(&account).unwrap();
+        assert_eq!(ser, r#"{"short_name":"Sandbox","author_name":"Anonymous","author_url":"https://t.me/"}"#);
+        let de: CreateAccount = serde_json::from_str(&ser).unwrap();
+        assert_eq!(de.author_url.unwrap().0, "https://t.me/");
+
+        let info = EditAccountInfo::new("1234567".into(), "Sandbox".into());
+        assert_eq!(serde_json::to_string(&info).unwrap(), r#"{"access_token":"1234567","short_name":"Sandbox"}"#);
+
+        let mut info = GetAccountInfo::new("1234567".into());
+        assert_eq!(serde_json::to_string(&info).unwrap(), r#"{"access_token":"1234567"}"#);
+        info.fields = Fields::new(vec!["page_count".into()]);
+        let ser = serde_json::to_string(&info).unwrap();
+        assert_eq!(ser, r#"{"access_token":"1234567","fields":["page_count"]}"#);
+        let de: GetAccountInfo = serde_json::from_str(r#"{"access_token":"1234567"}"#).unwrap();
+        assert_eq!(de.fields.0, vec!["short_name", "author_name", "author_url"]);
+
+        let revoke = RevokeAccessToken::new("1234567".into());
+        assert_eq!(serde_json::to_string(&revoke).unwrap(), r#"{"access_token":"1234567"}"#);
+    }
+
+    #[test]
+    fn test_page() {
+        let page = CreatePage::new("1234567".into(), "Sample Page".into(), Content::new(vec![Node::String("Hello, world!".into())]), false);
+        let ser: Value = serde_json::to_value(&page).unwrap();
+        let keys: Vec<&String> = ser.as_object().unwrap().keys().collect();
+        assert_eq!(keys, vec!["access_token", "content", "title"]);
+        let mut page: CreatePage = serde_json::from_value(ser).unwrap();
+        assert!(page.author_name.0.is_empty());
+        assert!(!page.return_content);
+        page.return_content = true;
+        page.author_name = AuthorName::new("Anonymous".into());
+        let ser: Value = serde_json::to_value(&page).unwrap();
+        assert_eq!(ser["return_content"], json!(true));
+        assert_eq!(ser["author_name"], json!("Anonymous"));
+
+        let page = EditPage::new("1234567".into(), "Sample-Page-12-15".into(), "Sample Page".into(), Content::new(vec![Node::String("Hello, world!".into())]), true);
+        let ser: Value = serde_json::to_value(&page).unwrap();
+        assert_eq!(ser["path"], json!("Sample-Page-12-15"));
+        let de: EditPage = serde_json::from_value(ser).unwrap();
+        assert_eq!(de.path, "Sample-Page-12-15");
+        assert!(de.return_content);
+
+        let page = GetPage::new("Sample-Page-12-15".into(), false);
+        assert_eq!(serde_json::to_string(&page).unwrap(), r#"{"path":"Sample-Page-12-15"}"#);
+    }
+
+    #[test]
+    fn test_list_views() {
+        let mut list = GetPageList::new("1234567".into(), 0);
+        assert_eq!(serde_json::to_string(&list).unwrap(), r#"{"access_token":"1234567"}"#);
+        list.offset = 10;
+        list.limit = Limit::with_val(3);
+        let ser = serde_json::to_string(&list).unwrap();
+        assert_eq!(ser, r#"{"access_token":"1234567","offset":10,"limit":3}"#);
+        let de: GetPageList = serde_json::from_str(r#"{"access_token":"1234567"}"#).unwrap();
+        assert_eq!(de.offset, 0);
+        assert_eq!(de.limit.0, 50);
+
+        let mut views = GetViews::new("Sample-Page-12-15".into(), 2016);
+        views.month = Some(Month::new(12));
+        let ser = serde_json::to_string(&views).unwrap();
+        assert_eq!(ser, r#"{"path":"Sample-Page-12-15","year":2016,"month":12}"#);
+        let de: GetViews = serde_json::from_str(&ser).unwrap();
+        assert_eq!(de.month.unwrap().0, 12);
+        assert!(de.day.is_none());
+        assert!(serde_json::from_str::<GetViews>(r#"{"path":"p","month":13}"#).is_err());
+    }
+}
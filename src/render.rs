@@ -0,0 +1,122 @@
+//! Rendering of content to HTML, e.g. to preview, mail or archive pages.
+use std::fmt::{self, Write};
+use crate::entity::{Content, Node, NodeElement, Page, Tag};
+
+impl Node {
+    /// Write the node and its children as escaped HTML. Void elements are self-closing, tags
+    /// outside of the API list only render their children and attributes other than href and src
+    /// are left out.
+    pub fn write_html<W: Write>(&self, out: &mut W) -> fmt::Result {
+        match self {
+            Node::String(text) => write_escaped(out, text, false),
+            Node::NodeElement(element) => element.write_html(out),
+        }
+    }
+}
+
+impl NodeElement {
+    /// Same as `Node::write_html`.
+    pub fn write_html<W: Write>(&self, out: &mut W) -> fmt::Result {
+        let children = self.children.as_deref().unwrap_or_default();
+        if let Tag::Unknown(_) = self.tag {
+            return write_nodes(out, children);
+        }
+        write!(out, "<{}", self.tag)?;
+        if let Some(attrs) = &self.attrs {
+            for (name, value) in [("href", &attrs.href), ("src", &attrs.src)] {
+                if let Some(value) = value {
+                    write!(out, " {}=\"", name)?;
+                    write_escaped(out, value, true)?;
+                    out.write_char('"')?;
+                }
+            }
+        }
+        if self.tag.is_void() {
+            return out.write_str("/>");
+        }
+        out.write_char('>')?;
+        write_nodes(out, children)?;
+        write!(out, "</{}>", self.tag)
+    }
+}
+
+impl Content {
+    /// The content as an HTML fragment, see `Node::write_html`.
+    pub fn to_html(&self) -> String {
+        let mut html = String::new();
+        write_nodes(&mut html, &self.0).expect("writing to a String never fails");
+        html
+    }
+}
+
+impl Page {
+    /// A standalone HTML document of the page: the title and description as `og` tags along with
+    /// `image_url`, then the title, the author line and the content, if it was returned.
+    pub fn to_html_document(&self) -> String {
+        let mut html = String::new();
+        self.write_html_document(&mut html).expect("writing to a String never fails");
+        html
+    }
+
+    fn write_html_document<W: Write>(&self, out: &mut W) -> fmt::Result {
+        out.write_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>")?;
+        write_escaped(out, &self.title.0, false)?;
+        out.write_str("</title>\n")?;
+        write_meta(out, "og:title", &self.title.0)?;
+        write_meta(out, "og:description", &self.description)?;
+        if let Some(image_url) = &self.image_url {
+            write_meta(out, "og:image", image_url)?;
+        }
+        out.write_str("</head>\n<body>\n<article>\n<h1>")?;
+        write_escaped(out, &self.title.0, false)?;
+        out.write_str("</h1>\n")?;
+        if let Some(author_name) = self.author_name.as_ref().filter(|name| !name.0.is_empty()) {
+            out.write_str("<address>")?;
+            match self.author_url.as_ref().filter(|url| !url.0.is_empty()) {
+                Some(author_url) => {
+                    out.write_str("<a href=\"")?;
+                    write_escaped(out, &author_url.0, true)?;
+                    out.write_str("\">")?;
+                    write_escaped(out, &author_name.0, false)?;
+                    out.write_str("</a>")?;
+                }
+                None => write_escaped(out, &author_name.0, false)?,
+            }
+            out.write_str("</address>\n")?;
+        }
+        if let Some(content) = &self.content {
+            write_nodes(out, content)?;
+            out.write_char('\n')?;
+        }
+        out.write_str("</article>\n</body>\n</html>\n")
+    }
+}
+
+fn write_nodes<W: Write>(out: &mut W, nodes: &[Node]) -> fmt::Result {
+    nodes.iter().try_for_each(|node| node.write_html(out))
+}
+
+fn write_meta<W: Write>(out: &mut W, property: &str, content: &str) -> fmt::Result {
+    write!(out, "<meta property=\"{}\" content=\"", property)?;
+    write_escaped(out, content, true)?;
+    out.write_str("\">\n")
+}
+
+/// Escape `&`, `<` and `>`, and quotes as well in attribute values.
+fn write_escaped<W: Write>(out: &mut W, text: &str, attr: bool) -> fmt::Result {
+    let mut start = 0;
+    for (i, c) in text.char_indices() {
+        let escaped = match c {
+            '&' => "&amp;",
+            '<' => "&lt;",
+            '>' => "&gt;",
+            '"' if attr => "&quot;",
+            '\'' if attr => "&#39;",
+            _ => continue,
+        };
+        out.write_str(&text[start..i])?;
+        out.write_str(escaped)?;
+        start = i + 1;
+    }
+    out.write_str(&text[start..])
+}
@@ -0,0 +1,39 @@
+#[cfg(test)]
+mod test_to_html {
+    use crate::entity::*;
+
+    #[test]
+    fn test_normal() {
+        let content = Content::new(crate::content![
+            h3["Intro"],
+            p["Hello, ", b["world"], br, a(href = "https://telegra.ph/?a=1&b=\"2\"")["<link>"]],
+            figure[img(src = "/file/1.jpg"), figcaption["Tom & Jerry's"]],
+            hr,
+        ]);
+        let html = r#"<h3>Intro</h3><p>Hello, <b>world</b><br/><a href="https://telegra.ph/?a=1&amp;b=&quot;2&quot;">&lt;link&gt;</a></p><figure><img src="/file/1.jpg"/><figcaption>Tom &amp; Jerry's</figcaption></figure><hr/>"#;
+        assert_eq!(content.to_html(), html);
+    }
+
+    #[test]
+    fn test_write_html() {
+        let mut out = String::from("> ");
+        crate::node!(code["a < b"]).write_html(&mut out).unwrap();
+        assert_eq!(out, "> <code>a &lt; b</code>");
+        let s1 = r#"[{"tag":"div","attrs":{"href":"/","onclick":"x"},"children":["kept ",{"tag":"i","children":["inside"]}]}]"#;
+        let content: Content = lenient(|| serde_json::from_str(s1)).unwrap();
+        assert_eq!(content.to_html(), "kept <i>inside</i>");
+    }
+
+    #[test]
+    fn test_document() {
+        let s1 = r#"{"path":"Sample-Page-12-15","url":"https://telegra.ph/Sample-Page-12-15","title":"Sample <Page>","description":"Hello, world!","author_name":"Anonymous","author_url":"https://t.me/x","image_url":"https://telegra.ph/file/1.jpg","content":[{"tag":"p","children":["Hello, world!"]}],"views":0}"#;
+        let page: Page = serde_json::from_str(s1).unwrap();
+        let html = page.to_html_document();
+        assert!(html.starts_with("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Sample &lt;Page&gt;</title>\n"));
+        assert!(html.contains("<meta property=\"og:title\" content=\"Sample &lt;Page&gt;\">\n"));
+        assert!(html.contains("<meta property=\"og:description\" content=\"Hello, world!\">\n"));
+        assert!(html.contains("<meta property=\"og:image\" content=\"https://telegra.ph/file/1.jpg\">\n"));
+        assert!(html.contains("<h1>Sample &lt;Page&gt;</h1>\n<address><a href=\"https://t.me/x\">Anonymous</a></address>\n<p>Hello, world!</p>\n</article>"));
+        assert!(html.ends_with("</html>\n"));
+    }
+}
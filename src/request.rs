@@ -0,0 +1,112 @@
+use std::fmt::Write;
+use crate::transport::Params;
+
+/// Base URL of the API, all queries must be served over HTTPS.
+pub const API_URL: &str = "https://api.telegra.ph";
+
+/// Longest url encoded parameters sent in a query string, beyond it they go in a POST body.
+/// Keeps the whole URL well below the 8 KB most servers and proxies accept.
+pub const MAX_QUERY_LEN: usize = 2048;
+
+/// Content type of the body of POST requests.
+pub const FORM_CONTENT_TYPE: &str = "application/x-www-form-urlencoded";
+
+/// HTTP method a request is sent with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HttpMethod {
+    Get,
+    Post,
+}
+
+/// A query to the API in a form any HTTP client can send:
+/// `https://api.telegra.ph/%method%/%path%?%query%`, or the same URL without query string and the
+/// parameters in a form body when sent as POST.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    /// Name of the API method, e.g. `getViews`.
+    pub method: &'static str,
+    /// Optional path following the method, e.g. `Sample-Page-12-15`.
+    pub path: Option<String>,
+    /// Parameters of the call, in order. Values are not yet url encoded.
+    pub params: Params,
+    /// GET unless the encoded parameters are longer than `MAX_QUERY_LEN`.
+    pub http_method: HttpMethod,
+}
+
+impl Request {
+    pub fn new(method: &'static str, path: Option<String>, params: Params) -> Self {
+        let http_method = if form_urlencode(&params).len() > MAX_QUERY_LEN {
+            HttpMethod::Post
+        } else {
+            HttpMethod::Get
+        };
+        Self {
+            method,
+            path,
+            params,
+            http_method,
+        }
+    }
+
+    /// Force the HTTP method instead of the one picked from the size of the parameters.
+    pub fn with_http_method(mut self, http_method: HttpMethod) -> Self {
+        self.http_method = http_method;
+        self
+    }
+
+    /// Full URL of the request below `base`, e.g. `https://api.telegra.ph`. The query string is
+    /// only present for GET requests.
+    pub fn url(&self, base: &str) -> String {
+        let mut url = format!("{}/{}", base.trim_end_matches('/'), self.method);
+        if let Some(ref path) = self.path {
+            url.push('/');
+            url.push_str(&encode_path(path));
+        }
+        if self.http_method == HttpMethod::Get && !self.params.is_empty() {
+            url.push('?');
+            url.push_str(&form_urlencode(&self.params));
+        }
+        url
+    }
+
+    /// The `application/x-www-form-urlencoded` body of POST requests.
+    pub fn body(&self) -> Option<String> {
+        match self.http_method {
+            HttpMethod::Post => Some(form_urlencode(&self.params)),
+            HttpMethod::Get => None,
+        }
+    }
+}
+
+/// Encode `pairs` as `application/x-www-form-urlencoded`: spaces become `+`, everything but
+/// alphanumerics and `*-._` is percent encoded.
+pub fn form_urlencode(pairs: &[(&str, String)]) -> String {
+    let mut out = String::new();
+    for (i, (key, val)) in pairs.iter().enumerate() {
+        if i > 0 {
+            out.push('&');
+        }
+        encode_into(&mut out, key, |b| b.is_ascii_alphanumeric() || b"*-._".contains(&b), true);
+        out.push('=');
+        encode_into(&mut out, val, |b| b.is_ascii_alphanumeric() || b"*-._".contains(&b), true);
+    }
+    out
+}
+
+fn encode_path(path: &str) -> String {
+    let mut out = String::new();
+    encode_into(&mut out, path, |b| b.is_ascii_alphanumeric() || b"-._~".contains(&b), false);
+    out
+}
+
+fn encode_into(out: &mut String, s: &str, keep: impl Fn(u8) -> bool, plus: bool) {
+    for b in s.bytes() {
+        if keep(b) {
+            out.push(b as char);
+        } else if plus && b == b' ' {
+            out.push('+');
+        } else {
+            let _ = write!(out, "%{:02X}", b);
+        }
+    }
+}
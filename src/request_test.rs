@@ -0,0 +1,122 @@
+#[cfg(test)]
+mod test_url {
+    use crate::methods::*;
+    use crate::entity::*;
+    use crate::request::*;
+
+    const TOKEN: &str = "b968da509bb76866c35425099bc0989a5ec3b32997d55286c657e6994bbb";
+
+    #[test]
+    fn test_account() {
+        let account = CreateAccount::with_raw("Sandbox".into(), Some("Anonymous".into()), None);
+        assert_eq!(account.to_request().url(API_URL), "https://api.telegra.ph/createAccount?short_name=Sandbox&author_name=Anonymous");
+
+        let info = EditAccountInfo::with_raw(TOKEN.into(), "Sandbox".into(), Some("Anonymous".into()), None);
+        assert_eq!(info.to_request().url(API_URL), format!("https://api.telegra.ph/editAccountInfo?access_token={}&short_name=Sandbox&author_name=Anonymous", TOKEN));
+
+        let mut info = GetAccountInfo::new(TOKEN.into());
+        info.fields = Fields::new(vec!["short_name".into(), "page_count".into()]);
+        let req = info.to_request();
+        assert_eq!(req.params[1], ("fields", r#"["short_name","page_count"]"#.to_string()));
+        assert_eq!(req.url(API_URL), format!("https://api.telegra.ph/getAccountInfo?access_token={}&fields=%5B%22short_name%22%2C%22page_count%22%5D", TOKEN));
+
+        let revoke = RevokeAccessToken::new(TOKEN.into());
+        assert_eq!(revoke.to_request().url(API_URL), format!("https://api.telegra.ph/revokeAccessToken?access_token={}", TOKEN));
+    }
+
+    #[test]
+    fn test_page() {
+        let content: Content = serde_json::from_str(r#"[{"tag":"p","children":["Hello, world!"]}]"#).unwrap();
+        let mut page = CreatePage::new(TOKEN.into(), "Sample Page".into(), content, true);
+        page.author_name = AuthorName::new("Anonymous".into());
+        let req = page.to_request();
+        assert_eq!(req.method, "createPage");
+        assert!(req.path.is_none());
+        assert!(req.body().is_none());
+        let keys: Vec<&str> = req.params.iter().map(|(k, _)| *k).collect();
+        assert_eq!(keys, vec!["access_token", "title", "author_name", "content", "return_content"]);
+        assert_eq!(req.params[3].1, r#"[{"tag":"p","children":["Hello, world!"]}]"#);
+        assert_eq!(req.url(API_URL), format!("https://api.telegra.ph/createPage?access_token={}&title=Sample+Page&author_name=Anonymous&content=%5B%7B%22tag%22%3A%22p%22%2C%22children%22%3A%5B%22Hello%2C+world%21%22%5D%7D%5D&return_content=true", TOKEN));
+
+        let page = EditPage::new(TOKEN.into(), "Sample-Page-12-15".into(), "Sample Page".into(), Content::new(vec![Node::String("Hello, world!".into())]), false);
+        let req = page.to_request();
+        assert_eq!(req.path.as_deref(), Some("Sample-Page-12-15"));
+        let keys: Vec<&str> = req.params.iter().map(|(k, _)| *k).collect();
+        assert_eq!(keys, vec!["access_token", "title", "content"]);
+        assert!(req.url(API_URL).starts_with("https://api.telegra.ph/editPage/Sample-Page-12-15?access_token="));
+
+        let page = GetPage::new("Sample-Page-12-15".into(), true);
+        assert_eq!(page.to_request().url(API_URL), "https://api.telegra.ph/getPage/Sample-Page-12-15?return_content=true");
+        let page = GetPage::new("Sample-Page-12-15".into(), false);
+        assert_eq!(page.to_request().url(API_URL), "https://api.telegra.ph/getPage/Sample-Page-12-15");
+    }
+
+    #[test]
+    fn test_list_views() {
+        let mut list = GetPageList::new(TOKEN.into(), 0);
+        list.limit = Limit::with_val(3);
+        assert_eq!(list.to_request().url(API_URL), format!("https://api.telegra.ph/getPageList?access_token={}&limit=3", TOKEN));
+        let list = GetPageList::new(TOKEN.into(), 10);
+        assert_eq!(list.to_request().url(API_URL), format!("https://api.telegra.ph/getPageList?access_token={}&offset=10", TOKEN));
+
+        let mut views = GetViews::new("Sample-Page-12-15".into(), 2016);
+        views.month = Some(Month::new(12));
+        assert_eq!(views.to_request().url(API_URL), "https://api.telegra.ph/getViews/Sample-Page-12-15?year=2016&month=12");
+        views.year = None;
+        views.month = None;
+        assert_eq!(views.to_request().url("https://api.telegra.ph/"), "https://api.telegra.ph/getViews/Sample-Page-12-15");
+    }
+
+    #[test]
+    fn test_encoding() {
+        let pairs = vec![("title", "Hello, wörld & co/1*2~".to_string()), ("empty", String::new())];
+        assert_eq!(form_urlencode(&pairs), "title=Hello%2C+w%C3%B6rld+%26+co%2F1*2%7E&empty=");
+        let req = Request::new("getPage", Some("Пример 12-15".into()), vec![]);
+        assert_eq!(req.url(API_URL), "https://api.telegra.ph/getPage/%D0%9F%D1%80%D0%B8%D0%BC%D0%B5%D1%80%2012-15");
+    }
+}
+
+#[cfg(test)]
+mod test_http_method {
+    use crate::methods::*;
+    use crate::entity::*;
+    use crate::request::*;
+
+    #[test]
+    fn test_auto() {
+        let page = GetPage::new("Sample-Page-12-15".into(), true);
+        let req = page.to_request();
+        assert_eq!(req.http_method, HttpMethod::Get);
+        assert!(req.body().is_none());
+
+        let text = "Lorem ipsum dolor sit amet. ".repeat(200);
+        let page = CreatePage::new("1234567".into(), "Long Read".into(), Content::new(vec![Node::String(text)]), false);
+        let req = page.to_request();
+        assert_eq!(req.http_method, HttpMethod::Post);
+        assert_eq!(req.url(API_URL), "https://api.telegra.ph/createPage");
+        let body = req.body().unwrap();
+        assert!(body.starts_with("access_token=1234567&title=Long+Read&content="));
+        assert!(body.len() > MAX_QUERY_LEN);
+    }
+
+    #[test]
+    fn test_override() {
+        let page = GetPage::new("Sample-Page-12-15".into(), true);
+        let req = page.to_request().with_http_method(HttpMethod::Post);
+        assert_eq!(req.url(API_URL), "https://api.telegra.ph/getPage/Sample-Page-12-15");
+        assert_eq!(req.body().as_deref(), Some("return_content=true"));
+
+        let page = CreatePage::new("1234567".into(), "Long Read".into(), Content::new(vec![Node::String("a".repeat(4096))]), false);
+        let req = page.to_request().with_http_method(HttpMethod::Get);
+        assert!(req.body().is_none());
+        assert!(req.url(API_URL).len() > 4096);
+    }
+
+    #[test]
+    fn test_threshold() {
+        let short = Request::new("getPage", None, vec![("p", "a".repeat(MAX_QUERY_LEN - 2))]);
+        assert_eq!(short.http_method, HttpMethod::Get);
+        let long = Request::new("getPage", None, vec![("p", "a".repeat(MAX_QUERY_LEN - 1))]);
+        assert_eq!(long.http_method, HttpMethod::Post);
+    }
+}
@@ -0,0 +1,171 @@
+//! Publication of content too large for a single page as several pages linked to each other.
+//!
+//! ```no_run
+//! # async fn run(transport: &impl telegra_ph::Transport, nodes: Vec<telegra_ph::Node>) -> telegra_ph::Ret<()> {
+//! use telegra_ph::split::MultiPage;
+//!
+//! // `nodes` may be well over the limit of a page, which `Content::new` would refuse.
+//! let report = MultiPage::split("Yearly report".into(), nodes)?;
+//! let pages = report.publish(transport, "b968da509bb76866c35425099bc0989a5ec3b32997d55286c657e6994bbb").await?;
+//! # Ok(())
+//! # }
+//! ```
+use crate::entity::{AuthorName, AuthorUrl, Content, Node, Page, Tag};
+use crate::error::ValidationError;
+use crate::limits;
+use crate::methods::{CreatePage, EditPage, Ret};
+use crate::transport::{self, BlockingTransport, Transport};
+
+/// Room left in every part by `MultiPage::split` for the navigation added once published.
+pub const NAV_RESERVE: usize = 2048;
+
+/// Longest path assumed for a page when checking its navigation before publishing: a slug of twice
+/// the longest title, for transliteration, followed by the date and a counter.
+const WORST_PATH: usize = 2 * limits::TITLE.max + "-12-31-9999".len();
+
+/// Content cut into parts published as one page each, in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiPage {
+    pub title: String,
+    pub author_name: AuthorName,
+    pub author_url: AuthorUrl,
+    pub parts: Vec<Content>,
+}
+
+impl MultiPage {
+    /// Split `nodes` into parts that fit `limits::CONTENT_SIZE` along with their navigation.
+    pub fn split(title: String, nodes: Vec<Node>) -> Result<Self, ValidationError> {
+        Self::split_at_size(title, nodes, limits::CONTENT_SIZE - NAV_RESERVE)
+    }
+
+    /// Split `nodes` into parts of at most `max_size` encoded bytes. Parts are only cut between
+    /// blocks, inline nodes following each other at the top level stay together. Fails if a
+    /// single block is over `max_size`.
+    pub fn split_at_size(title: String, nodes: Vec<Node>, max_size: usize) -> Result<Self, ValidationError> {
+        limits::TITLE.check(&title)?;
+        let mut parts = Vec::new();
+        let mut part: Vec<Node> = Vec::new();
+        // Size of the part encoded as a JSON array, brackets included.
+        let mut size = 2;
+        for unit in units(nodes) {
+            let unit_size = unit.iter().map(Node::len).sum::<usize>() + unit.len() - 1;
+            if 2 + unit_size > max_size {
+                return Err(ValidationError::new("content", format!("blocks fitting {} bytes", max_size), format!("a block taking {} bytes", 2 + unit_size)));
+            }
+            let separator = usize::from(!part.is_empty());
+            if size + separator + unit_size > max_size {
+                parts.push(Content(std::mem::take(&mut part)));
+                size = 2;
+            }
+            size += usize::from(!part.is_empty()) + unit_size;
+            part.extend(unit);
+        }
+        if !part.is_empty() {
+            parts.push(Content(part));
+        }
+        Ok(Self {
+            title,
+            author_name: AuthorName::default(),
+            author_url: AuthorUrl::default(),
+            parts,
+        })
+    }
+
+    /// Create one page per part, then edit each of them to add "Part N of M" and links to the
+    /// previous and next parts. Returns the pages in order, as last edited. Every part is checked,
+    /// with the longest navigation it can get, before the first page is created; a failure past
+    /// that point leaves the pages already created as they are.
+    pub async fn publish<T: Transport + ?Sized>(&self, transport: &T, access_token: &str) -> Ret<Vec<Page>> {
+        let mut pages = Vec::new();
+        for page in self.check(access_token)? {
+            pages.push(transport::execute(transport, &page).await?);
+        }
+        if pages.len() < 2 {
+            return Ok(pages);
+        }
+        let urls: Vec<String> = pages.iter().map(|page| page.url.clone()).collect();
+        let mut edited = Vec::new();
+        for (i, page) in pages.iter().enumerate() {
+            edited.push(transport::execute(transport, &self.edit_page(access_token, &page.path, &urls, i)?).await?);
+        }
+        Ok(edited)
+    }
+
+    /// Same as `publish`, through a blocking transport.
+    pub fn publish_blocking<T: BlockingTransport + ?Sized>(&self, transport: &T, access_token: &str) -> Ret<Vec<Page>> {
+        let mut pages = Vec::new();
+        for page in self.check(access_token)? {
+            pages.push(transport::execute_blocking(transport, &page)?);
+        }
+        if pages.len() < 2 {
+            return Ok(pages);
+        }
+        let urls: Vec<String> = pages.iter().map(|page| page.url.clone()).collect();
+        let mut edited = Vec::new();
+        for (i, page) in pages.iter().enumerate() {
+            edited.push(transport::execute_blocking(transport, &self.edit_page(access_token, &page.path, &urls, i)?)?);
+        }
+        Ok(edited)
+    }
+
+    /// The pages to create, once every part has been checked alone and with the navigation of the
+    /// longest URLs.
+    fn check(&self, access_token: &str) -> Result<Vec<CreatePage>, ValidationError> {
+        let pages = self.parts.iter().map(|part| self.create_page(access_token, part)).collect::<Result<Vec<_>, _>>()?;
+        if self.parts.len() > 1 {
+            let path = "x".repeat(WORST_PATH);
+            let urls = vec![format!("https://telegra.ph/{}", path); self.parts.len()];
+            for i in 0..self.parts.len() {
+                self.edit_page(access_token, &path, &urls, i)?;
+            }
+        }
+        Ok(pages)
+    }
+
+    fn create_page(&self, access_token: &str, part: &Content) -> Result<CreatePage, ValidationError> {
+        let mut page = CreatePage::try_new(access_token.into(), self.title.clone(), part.clone(), false)?;
+        page.author_name = self.author_name.clone();
+        page.author_url = self.author_url.clone();
+        Ok(page)
+    }
+
+    fn edit_page(&self, access_token: &str, path: &str, urls: &[String], i: usize) -> Result<EditPage, ValidationError> {
+        let mut content = self.parts[i].clone();
+        content.0.push(navigation(urls, i));
+        let mut page = EditPage::try_new(access_token.into(), path.into(), self.title.clone(), content, false)?;
+        page.author_name = self.author_name.clone();
+        page.author_url = self.author_url.clone();
+        Ok(page)
+    }
+}
+
+/// Top level nodes grouped into the units a part can be cut between: a block on its own, or a run
+/// of inline nodes.
+fn units(nodes: Vec<Node>) -> Vec<Vec<Node>> {
+    let mut units: Vec<Vec<Node>> = Vec::new();
+    let mut inline = false;
+    for node in nodes {
+        let block = matches!(&node, Node::NodeElement(e) if e.tag.is_block() || matches!(e.tag, Tag::Iframe | Tag::Video));
+        match units.last_mut() {
+            Some(unit) if inline && !block => unit.push(node),
+            _ => units.push(vec![node]),
+        }
+        inline = !block;
+    }
+    units
+}
+
+/// The paragraph ending part `i` of the parts at `urls`: its number and links to the parts around
+/// it.
+fn navigation(urls: &[String], i: usize) -> Node {
+    let mut children = vec![Node::text(format!("Part {} of {}", i + 1, urls.len()))];
+    if i > 0 {
+        children.push(Node::text(" · "));
+        children.push(crate::node!(a(href = urls[i - 1].clone())["« Previous"]));
+    }
+    if i + 1 < urls.len() {
+        children.push(Node::text(" · "));
+        children.push(crate::node!(a(href = urls[i + 1].clone())["Next »"]));
+    }
+    Node::element(Tag::P, None, children)
+}
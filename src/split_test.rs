@@ -0,0 +1,172 @@
+#[cfg(test)]
+mod test_split {
+    use crate::entity::*;
+    use crate::split::*;
+
+    fn paragraphs(n: usize) -> Vec<Node> {
+        (0..n).map(|i| crate::node!(p[{format!("Paragraph {:02} {}", i, "x".repeat(60))}])).collect()
+    }
+
+    #[test]
+    fn test_normal() {
+        let content = Content::new(paragraphs(10));
+        let block = content.0[0].len();
+        let report = MultiPage::split_at_size("Report".into(), content.0.clone(), 3 * block + 4).unwrap();
+        assert_eq!(report.parts.len(), 4);
+        assert!(report.parts.iter().all(|part| part.encoded_size() <= 3 * block + 4));
+        assert_eq!(report.parts[0].encoded_size(), 3 * block + 4);
+        let joined: Vec<Node> = report.parts.iter().flat_map(|part| part.0.clone()).collect();
+        assert_eq!(joined, content.0);
+        let report = MultiPage::split("Report".into(), content.0.clone()).unwrap();
+        assert_eq!(report.parts, vec![content]);
+    }
+
+    #[test]
+    fn test_inline_runs() {
+        let content = crate::content![h3["Title"], "some ", b["bold"], " text", hr, "tail"];
+        let report = MultiPage::split_at_size("Report".into(), content, 60).unwrap();
+        let parts: Vec<String> = report.parts.iter().map(|part| serde_json::to_string(part).unwrap()).collect();
+        assert_eq!(parts, vec![
+            r#"[{"tag":"h3","children":["Title"]}]"#,
+            r#"["some ",{"tag":"b","children":["bold"]}," text"]"#,
+            r#"[{"tag":"hr"},"tail"]"#,
+        ]);
+    }
+
+    #[test]
+    fn test_errors() {
+        let e = MultiPage::split_at_size("Report".into(), paragraphs(2), 50).err().unwrap();
+        assert_eq!(e.field, "content");
+        assert!(e.to_string().starts_with("content: expected blocks fitting 50 bytes, got a block taking "));
+        let e = MultiPage::split("".into(), paragraphs(2)).err().unwrap();
+        assert_eq!(e.field, "title");
+    }
+}
+
+#[cfg(test)]
+mod test_publish {
+    use crate::entity::*;
+    use crate::limits;
+    use crate::methods::Ret;
+    use crate::request::*;
+    use crate::split::*;
+    use crate::transport::*;
+    use std::pin::Pin;
+    use std::future::Future;
+    use std::sync::Mutex;
+    use futures::executor::block_on;
+
+    /// Answers every createPage with a new path and echoes the path of editPage.
+    #[derive(Default)]
+    struct Mock {
+        calls: Mutex<Vec<Request>>,
+    }
+
+    impl Mock {
+        fn respond(&self, request: Request) -> String {
+            let mut calls = self.calls.lock().unwrap();
+            let path = match &request.path {
+                Some(path) => path.clone(),
+                None => format!("Report-10-18-{}", calls.len() + 1),
+            };
+            calls.push(request);
+            format!(r#"{{"ok":true,"result":{{"path":"{0}","url":"https://telegra.ph/{0}","title":"Report","description":"","views":0}}}}"#, path)
+        }
+    }
+
+    impl Transport for Mock {
+        fn call<'a>(&'a self, request: Request) -> Pin<Box<dyn Future<Output = Ret<String>> + Send + 'a>> {
+            Box::pin(futures::future::ready(Ok(self.respond(request))))
+        }
+    }
+
+    impl BlockingTransport for Mock {
+        fn call(&self, request: Request) -> Ret<String> {
+            Ok(self.respond(request))
+        }
+    }
+
+    fn report() -> MultiPage {
+        let content = crate::content![p["one"], p["two"], p["three"]];
+        let mut report = MultiPage::split_at_size("Report".into(), content, 40).unwrap();
+        report.author_name = AuthorName::new("Anonymous".into());
+        report
+    }
+
+    fn param<'a>(request: &'a Request, key: &str) -> &'a str {
+        &request.params.iter().find(|(k, _)| *k == key).unwrap().1
+    }
+
+    #[test]
+    fn test_publish() {
+        let mock = Mock::default();
+        let pages = block_on(report().publish(&mock, "1234567")).unwrap();
+        let paths: Vec<&str> = pages.iter().map(|page| page.path.as_str()).collect();
+        assert_eq!(paths, vec!["Report-10-18-1", "Report-10-18-2", "Report-10-18-3"]);
+        let calls = mock.calls.lock().unwrap();
+        let methods: Vec<&str> = calls.iter().map(|call| call.method).collect();
+        assert_eq!(methods, vec!["createPage", "createPage", "createPage", "editPage", "editPage", "editPage"]);
+        assert_eq!(param(&calls[0], "content"), r#"[{"tag":"p","children":["one"]}]"#);
+        assert_eq!(param(&calls[0], "author_name"), "Anonymous");
+        assert_eq!(calls[3].path.as_deref(), Some("Report-10-18-1"));
+        assert_eq!(param(&calls[3], "content"), r#"[{"tag":"p","children":["one"]},{"tag":"p","children":["Part 1 of 3"," · ",{"tag":"a","attrs":{"href":"https://telegra.ph/Report-10-18-2"},"children":["Next »"]}]}]"#);
+        assert_eq!(param(&calls[4], "content"), r#"[{"tag":"p","children":["two"]},{"tag":"p","children":["Part 2 of 3"," · ",{"tag":"a","attrs":{"href":"https://telegra.ph/Report-10-18-1"},"children":["« Previous"]}," · ",{"tag":"a","attrs":{"href":"https://telegra.ph/Report-10-18-3"},"children":["Next »"]}]}]"#);
+        assert_eq!(param(&calls[5], "author_name"), "Anonymous");
+    }
+
+    #[test]
+    fn test_single_part() {
+        let mock = Mock::default();
+        let report = MultiPage::split("Report".into(), crate::content![p["one"]]).unwrap();
+        let pages = report.publish_blocking(&mock, "1234567").unwrap();
+        assert_eq!(pages.len(), 1);
+        assert_eq!(mock.calls.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_checked_first() {
+        let mock = Mock::default();
+        let content = crate::content![p["one"], p["two"], li["three"]];
+        let report = MultiPage::split_at_size("Report".into(), content, 40).unwrap();
+        assert_eq!(report.parts.len(), 3);
+        assert!(block_on(report.publish(&mock, "1234567")).is_err());
+        assert!(report.publish_blocking(&mock, "1234567").is_err());
+        assert!(mock.calls.lock().unwrap().is_empty());
+
+        // Fits a page alone, but not with the navigation.
+        let nodes: Vec<Node> = (0..800).map(|i| crate::node!(p[{format!("Paragraph {:04} {}", i, "x".repeat(80))}])).collect();
+        let report = MultiPage::split_at_size("Report".into(), nodes, limits::CONTENT_SIZE - 100).unwrap();
+        assert_eq!(report.parts.len(), 2);
+        assert!(report.parts.iter().all(|part| part.check_size().is_ok()));
+        assert!(report.publish_blocking(&mock, "1234567").is_err());
+        assert!(mock.calls.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_oversized() {
+        let nodes: Vec<Node> = (0..2000).map(|i| crate::node!(p[{format!("Paragraph {:04} {}", i, "x".repeat(80))}])).collect();
+        assert!(Content::try_new(nodes.clone()).is_err());
+        let report = MultiPage::split("Report".into(), nodes.clone()).unwrap();
+        assert_eq!(report.parts.len(), 4);
+        assert!(report.parts.iter().all(|part| part.encoded_size() + NAV_RESERVE <= limits::CONTENT_SIZE));
+        let joined: Vec<Node> = report.parts.iter().flat_map(|part| part.0.clone()).collect();
+        assert_eq!(joined, nodes);
+        let mock = Mock::default();
+        block_on(report.publish(&mock, "1234567")).unwrap();
+        let calls = mock.calls.lock().unwrap();
+        // Each part as edited, with its navigation paragraph.
+        for call in &calls[4..] {
+            let content: Content = serde_json::from_str(param(call, "content")).unwrap();
+            assert!(content.encoded_size() <= limits::CONTENT_SIZE);
+            assert!(content.check_size().is_ok());
+        }
+    }
+
+    #[test]
+    fn test_publish_blocking() {
+        let mock = Mock::default();
+        let pages = report().publish_blocking(&mock, "1234567").unwrap();
+        assert_eq!(pages.len(), 3);
+        assert_eq!(mock.calls.lock().unwrap().len(), 6);
+    }
+}
@@ -0,0 +1,75 @@
+//! A stand-in for the API, serving canned responses over plain HTTP on a local port.
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// What the stand-in received.
+#[derive(Debug, Clone)]
+pub(crate) struct Received {
+    pub request_line: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Received {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter().find(|(k, _)| k.eq_ignore_ascii_case(name)).map(|(_, v)| v.as_str())
+    }
+}
+
+pub(crate) struct StandIn {
+    pub base_url: String,
+    pub received: Arc<Mutex<Vec<Received>>>,
+}
+
+/// Serve each of `responses` as `(status, body)` to one connection, in order, waiting `delay`
+/// before answering.
+pub(crate) fn serve(responses: Vec<(u16, &'static str)>, delay: Duration) -> StandIn {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let received = Arc::new(Mutex::new(vec![]));
+    let log = received.clone();
+    thread::spawn(move || {
+        for (status, body) in responses {
+            let (mut stream, _) = match listener.accept() {
+                Ok(conn) => conn,
+                Err(_) => return,
+            };
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut headers = vec![];
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let line = line.trim_end();
+                if line.is_empty() {
+                    break;
+                }
+                if let Some((k, v)) = line.split_once(':') {
+                    headers.push((k.trim().to_string(), v.trim().to_string()));
+                }
+            }
+            let len = headers.iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case("content-length"))
+                .map(|(_, v)| v.parse().unwrap())
+                .unwrap_or(0);
+            let mut buf = vec![0; len];
+            reader.read_exact(&mut buf).unwrap();
+            log.lock().unwrap().push(Received {
+                request_line: request_line.trim_end().to_string(),
+                headers,
+                body: String::from_utf8(buf).unwrap(),
+            });
+            thread::sleep(delay);
+            let _ = write!(
+                stream,
+                "HTTP/1.1 {} Stand-In\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status, body.len(), body
+            );
+        }
+    });
+    StandIn { base_url, received }
+}
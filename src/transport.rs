@@ -0,0 +1,89 @@
+use std::pin::Pin;
+use std::future::Future;
+use serde::{de::DeserializeOwned, Serialize};
+use crate::entity::ApiResponse;
+use crate::methods::{Ret, TelegraphMethod};
+use crate::request::Request;
+
+/// Parameters of a call as name/value pairs, already in their wire form: `content` and `fields`
+/// are JSON encoded, numbers and booleans are plain text.
+pub type Params = Vec<(&'static str, String)>;
+
+/// Something able to deliver a call to the Telegraph API, e.g. a thin wrapper around an HTTP
+/// client. The request carries the API method name (e.g. `createAccount`), the optional path
+/// following it (e.g. `Sample-Page-12-15` for `getPage`) and the parameters of the call, see
+/// `Request::url`, `Request::http_method` and `Request::body` for what to send.
+///
+/// On success, the raw JSON body of the response is returned, untouched. Transports and the
+/// futures they return are `Send`, so calls can be spawned on multi-threaded runtimes.
+pub trait Transport: Send + Sync {
+    fn call<'a>(&'a self, request: Request) -> Pin<Box<dyn Future<Output = Ret<String>> + Send + 'a>>;
+
+    /// True if the responses delivered by this transport are decoded leniently, see
+    /// `entity::lenient`. False by default.
+    fn is_lenient(&self) -> bool {
+        false
+    }
+}
+
+/// The blocking counterpart of `Transport`, for code running outside of any async runtime.
+pub trait BlockingTransport {
+    fn call(&self, request: Request) -> Ret<String>;
+
+    /// Same as `Transport::is_lenient`.
+    fn is_lenient(&self) -> bool {
+        false
+    }
+}
+
+/// Send `request` through `transport` and decode the result of the response. The `send` method
+/// of each request calls it with `to_request()`, call it directly to tweak the request first,
+/// e.g. to force its HTTP method.
+pub async fn send<T, R>(transport: &R, request: Request) -> Ret<T>
+    where
+        T: DeserializeOwned,
+        R: Transport + ?Sized,
+{
+    let raw = transport.call(request).await?;
+    decode(&raw, transport.is_lenient())
+}
+
+/// Send `method` through `transport` and decode its response, for any method of the API.
+pub async fn execute<M, R>(transport: &R, method: &M) -> Ret<M::Response>
+    where
+        M: TelegraphMethod + ?Sized,
+        R: Transport + ?Sized,
+{
+    send(transport, method.to_request()).await
+}
+
+/// The blocking counterpart of `execute`.
+pub fn execute_blocking<M, R>(transport: &R, method: &M) -> Ret<M::Response>
+    where
+        M: TelegraphMethod + ?Sized,
+        R: BlockingTransport + ?Sized,
+{
+    send_blocking(transport, method.to_request())
+}
+
+/// The blocking counterpart of `send`.
+pub fn send_blocking<T, R>(transport: &R, request: Request) -> Ret<T>
+    where
+        T: DeserializeOwned,
+        R: BlockingTransport + ?Sized,
+{
+    let raw = transport.call(request)?;
+    decode(&raw, transport.is_lenient())
+}
+
+fn decode<T: DeserializeOwned>(raw: &str, lenient: bool) -> Ret<T> {
+    if lenient {
+        ApiResponse::parse_lenient(raw)
+    } else {
+        ApiResponse::parse(raw)
+    }
+}
+
+pub(crate) fn to_json<T: Serialize>(value: &T) -> String {
+    serde_json::to_string(value).expect("parameter always serializes to JSON")
+}
@@ -0,0 +1,162 @@
+//! Depth-first traversal and simple queries over content trees.
+//!
+//! ```
+//! use telegra_ph::{content, Content, Tag};
+//!
+//! let content = Content::new(content![p["See ", a(href = "https://telegra.ph/")["this"]], h3["End"]]);
+//! let links: Vec<&str> = content.find_all(Tag::A).filter_map(|a| a.attrs.as_ref()?.href.as_deref()).collect();
+//! assert_eq!(links, ["https://telegra.ph/"]);
+//! assert_eq!(content.text_nodes().collect::<String>(), "See thisEnd");
+//! ```
+use std::slice;
+use crate::entity::{Content, Node, NodeElement, Tag};
+
+/// Condition on elements used by `find_all` and `find`: a tag, or any predicate.
+pub trait Selector {
+    fn matches(&self, element: &NodeElement) -> bool;
+}
+
+impl Selector for Tag {
+    fn matches(&self, element: &NodeElement) -> bool {
+        element.tag == *self
+    }
+}
+
+impl Selector for &Tag {
+    fn matches(&self, element: &NodeElement) -> bool {
+        element.tag == **self
+    }
+}
+
+impl<F: Fn(&NodeElement) -> bool> Selector for F {
+    fn matches(&self, element: &NodeElement) -> bool {
+        self(element)
+    }
+}
+
+/// Iterator over nodes in document order, each followed by its children, see `descendants`.
+pub struct Descendants<'a> {
+    stack: Vec<slice::Iter<'a, Node>>,
+}
+
+impl<'a> Descendants<'a> {
+    fn new(nodes: &'a [Node]) -> Self {
+        Self { stack: vec![nodes.iter()] }
+    }
+}
+
+impl<'a> Iterator for Descendants<'a> {
+    type Item = &'a Node;
+
+    fn next(&mut self) -> Option<&'a Node> {
+        loop {
+            let node = match self.stack.last_mut()?.next() {
+                Some(node) => node,
+                None => {
+                    self.stack.pop();
+                    continue;
+                }
+            };
+            if let Node::NodeElement(NodeElement { children: Some(children), .. }) = node {
+                self.stack.push(children.iter());
+            }
+            return Some(node);
+        }
+    }
+}
+
+impl Content {
+    /// Every node of the tree, depth first.
+    pub fn descendants(&self) -> Descendants<'_> {
+        Descendants::new(&self.0)
+    }
+
+    /// The text of every text node, in document order.
+    pub fn text_nodes(&self) -> impl Iterator<Item = &str> {
+        text_nodes(self.descendants())
+    }
+
+    /// Every element with the given tag, depth first.
+    pub fn elements_by_tag(&self, tag: Tag) -> impl Iterator<Item = &NodeElement> {
+        self.find_all(tag)
+    }
+
+    /// Every element matching `selector`, depth first.
+    pub fn find_all<S: Selector>(&self, selector: S) -> impl Iterator<Item = &NodeElement> {
+        find_all(self.descendants(), selector)
+    }
+
+    /// The first element matching `selector`.
+    pub fn find<S: Selector>(&self, selector: S) -> Option<&NodeElement> {
+        self.find_all(selector).next()
+    }
+
+    /// Call `f` on every node, depth first, with the indices leading to it from the top of the
+    /// content as in `Violation::path`. Children are visited after `f` returns for their parent, so
+    /// nodes it adds are visited too.
+    pub fn walk_mut<F: FnMut(&[usize], &mut Node)>(&mut self, mut f: F) {
+        let mut path = Vec::new();
+        walk_mut(&mut self.0, &mut path, &mut f);
+    }
+}
+
+impl Node {
+    /// Every node below this one, depth first, itself excluded.
+    pub fn descendants(&self) -> Descendants<'_> {
+        match self {
+            Node::NodeElement(NodeElement { children: Some(children), .. }) => Descendants::new(children),
+            _ => Descendants::new(&[]),
+        }
+    }
+
+    /// The text of this node and of every text node below it, in document order.
+    pub fn text_nodes(&self) -> impl Iterator<Item = &str> {
+        text_nodes(std::iter::once(self).chain(self.descendants()))
+    }
+
+    /// This element and every element below it with the given tag, depth first.
+    pub fn elements_by_tag(&self, tag: Tag) -> impl Iterator<Item = &NodeElement> {
+        self.find_all(tag)
+    }
+
+    /// This element and every element below it matching `selector`, depth first.
+    pub fn find_all<S: Selector>(&self, selector: S) -> impl Iterator<Item = &NodeElement> {
+        find_all(std::iter::once(self).chain(self.descendants()), selector)
+    }
+
+    /// Same as `Content::walk_mut`, this node included with an empty path.
+    pub fn walk_mut<F: FnMut(&[usize], &mut Node)>(&mut self, mut f: F) {
+        let mut path = Vec::new();
+        walk_mut(slice::from_mut(self), &mut path, &mut |path, node| f(&path[1..], node));
+    }
+}
+
+fn text_nodes<'a>(nodes: impl Iterator<Item = &'a Node>) -> impl Iterator<Item = &'a str> {
+    nodes.filter_map(|node| match node {
+        Node::String(text) => Some(text.as_str()),
+        Node::NodeElement(_) => None,
+    })
+}
+
+/// The text of `nodes` and of everything below them, concatenated.
+pub(crate) fn text_of(nodes: &[Node]) -> String {
+    text_nodes(Descendants::new(nodes)).collect()
+}
+
+fn find_all<'a, S: Selector>(nodes: impl Iterator<Item = &'a Node>, selector: S) -> impl Iterator<Item = &'a NodeElement> {
+    nodes.filter_map(move |node| match node {
+        Node::NodeElement(element) if selector.matches(element) => Some(element),
+        _ => None,
+    })
+}
+
+fn walk_mut<F: FnMut(&[usize], &mut Node)>(nodes: &mut [Node], path: &mut Vec<usize>, f: &mut F) {
+    for (i, node) in nodes.iter_mut().enumerate() {
+        path.push(i);
+        f(path, node);
+        if let Node::NodeElement(NodeElement { children: Some(children), .. }) = node {
+            walk_mut(children, path, f);
+        }
+        path.pop();
+    }
+}
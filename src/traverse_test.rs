@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod test_traverse {
+    use crate::entity::*;
+
+    fn sample() -> Content {
+        Content::new(crate::content![
+            h3["Intro"],
+            p["See ", a(href = "https://a.example/")["one ", b["bold"]], " and ", a(href = "https://b.example/")["two"]],
+            figure[img(src = "/file/1.jpg"), figcaption["Caption"]],
+            ul[li["x", ol[li[a(href = "https://c.example/")["three"]]]]],
+        ])
+    }
+
+    #[test]
+    fn test_descendants() {
+        let content = sample();
+        let tags: Vec<&str> = content.descendants().filter_map(|node| match node {
+            Node::NodeElement(e) => Some(e.tag.as_str()),
+            Node::String(_) => None,
+        }).collect();
+        assert_eq!(tags, ["h3", "p", "a", "b", "a", "figure", "img", "figcaption", "ul", "li", "ol", "li", "a"]);
+        assert_eq!(content.descendants().count(), 22);
+        let texts: Vec<&str> = content.text_nodes().collect();
+        assert_eq!(texts, ["Intro", "See ", "one ", "bold", " and ", "two", "Caption", "x", "three"]);
+        assert_eq!(content.0[1].descendants().count(), 8);
+        assert_eq!(content.0[1].text_nodes().collect::<String>(), "See one bold and two");
+        assert_eq!(Node::text("alone").text_nodes().collect::<Vec<_>>(), ["alone"]);
+        assert_eq!(Node::text("alone").descendants().count(), 0);
+    }
+
+    #[test]
+    fn test_find() {
+        let content = sample();
+        let links: Vec<&str> = content.find_all(Tag::A).filter_map(|a| a.attrs.as_ref()?.href.as_deref()).collect();
+        assert_eq!(links, ["https://a.example/", "https://b.example/", "https://c.example/"]);
+        assert_eq!(content.elements_by_tag(Tag::Li).count(), 2);
+        assert_eq!(content.find(&Tag::Img).and_then(|img| img.attrs.as_ref()?.src.as_deref()), Some("/file/1.jpg"));
+        assert!(content.find(Tag::Video).is_none());
+        let with_children = content.find_all(|e: &NodeElement| e.children.as_ref().is_some_and(|c| c.len() > 1)).count();
+        assert_eq!(with_children, 4);
+        assert_eq!(content.0[3].find_all(Tag::Ul).count(), 1);
+        assert_eq!(content.0[3].elements_by_tag(Tag::A).count(), 1);
+    }
+
+    #[test]
+    fn test_walk_mut() {
+        let mut content = sample();
+        let mut paths = Vec::new();
+        content.walk_mut(|path, node| {
+            if let Node::NodeElement(e) = node {
+                if e.tag == Tag::Img {
+                    paths.push(path.to_vec());
+                    e.attrs = Some(Attrs::src("https://cdn.example/1.jpg"));
+                }
+                if e.tag == Tag::H3 {
+                    e.tag = Tag::H4;
+                }
+            }
+        });
+        assert_eq!(paths, [vec![2, 0]]);
+        assert!(content.find(Tag::H3).is_none());
+        assert_eq!(content.find(Tag::Img).unwrap().attrs, Some(Attrs::src("https://cdn.example/1.jpg")));
+
+        let mut list = content.0[3].clone();
+        let mut visited = Vec::new();
+        list.walk_mut(|path, node| {
+            visited.push(path.to_vec());
+            if let Node::String(text) = node {
+                text.make_ascii_uppercase();
+            }
+        });
+        assert_eq!(visited, [vec![], vec![0], vec![0, 0], vec![0, 1], vec![0, 1, 0], vec![0, 1, 0, 0], vec![0, 1, 0, 0, 0]]);
+        assert_eq!(list.text_nodes().collect::<Vec<_>>(), ["X", "THREE"]);
+    }
+}
@@ -0,0 +1,119 @@
+//! Structural checks of a content tree, for mistakes the API does not reject but silently mangles.
+use std::fmt;
+use crate::entity::{Content, Node, NodeElement, Tag};
+use crate::error::ValidationError;
+
+/// A problem found in a content tree by `Content::validate`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// Indices leading to the node from the top of the content, e.g. `[2, 0]` for the first child
+    /// of the third node.
+    pub path: Vec<usize>,
+    pub kind: ViolationKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ViolationKind {
+    /// A tag outside of the list the API accepts, kept by lenient decoding.
+    UnknownTag(String),
+    /// An attribute other than href and src, kept by lenient decoding.
+    UnknownAttr(String),
+    /// The element needs the attribute to be displayed, e.g. `src` of `img`.
+    MissingAttr(Tag, &'static str),
+    /// The element never has children, e.g. `br`.
+    UnexpectedChildren(Tag),
+    /// The element is only valid inside one of the given parents, e.g. `li` inside `ol` or `ul`.
+    Misplaced(Tag, &'static [Tag]),
+    /// A block element inside a paragraph.
+    BlockInParagraph(Tag),
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path: Vec<String> = self.path.iter().map(usize::to_string).collect();
+        write!(f, "node {}: {}", path.join("."), self.kind)
+    }
+}
+
+impl fmt::Display for ViolationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ViolationKind::UnknownTag(tag) => write!(f, "unknown tag {:?}", tag),
+            ViolationKind::UnknownAttr(attr) => write!(f, "unknown attribute {:?}", attr),
+            ViolationKind::MissingAttr(tag, attr) => write!(f, "{} without {}", tag, attr),
+            ViolationKind::UnexpectedChildren(tag) => write!(f, "{} with children", tag),
+            ViolationKind::Misplaced(tag, parents) => {
+                let parents: Vec<&str> = parents.iter().map(Tag::as_str).collect();
+                write!(f, "{} outside of {}", tag, parents.join(" or "))
+            }
+            ViolationKind::BlockInParagraph(tag) => write!(f, "{} inside p", tag),
+        }
+    }
+}
+
+impl Content {
+    /// Walk the whole tree and report every structural problem in it, in document order.
+    pub fn validate(&self) -> Result<(), Vec<Violation>> {
+        let mut violations = Vec::new();
+        let mut path = Vec::new();
+        validate_nodes(&self.0, None, false, &mut path, &mut violations);
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+
+    /// Same as `validate`, with the violations folded into a single `ValidationError`.
+    pub fn check(&self) -> Result<(), ValidationError> {
+        self.validate().map_err(|violations| {
+            let actual: Vec<String> = violations.iter().map(Violation::to_string).collect();
+            ValidationError::new("content", "well-formed node tree", actual.join("; "))
+        })
+    }
+}
+
+/// `in_paragraph` is true below a p, until a block element, which is reported itself, starts a new
+/// flow.
+fn validate_nodes(nodes: &[Node], parent: Option<&Tag>, in_paragraph: bool, path: &mut Vec<usize>, violations: &mut Vec<Violation>) {
+    for (i, node) in nodes.iter().enumerate() {
+        if let Node::NodeElement(element) = node {
+            path.push(i);
+            validate_element(element, parent, in_paragraph, path, violations);
+            path.pop();
+        }
+    }
+}
+
+fn validate_element(element: &NodeElement, parent: Option<&Tag>, in_paragraph: bool, path: &mut Vec<usize>, violations: &mut Vec<Violation>) {
+    let mut report = |kind| violations.push(Violation { path: path.clone(), kind });
+    let tag = &element.tag;
+    if let Tag::Unknown(name) = tag {
+        report(ViolationKind::UnknownTag(name.clone()));
+    }
+    if let Some(attrs) = &element.attrs {
+        for key in attrs.unknown.keys() {
+            report(ViolationKind::UnknownAttr(key.clone()));
+        }
+    }
+    let has = |attr| element.attrs.as_ref().and_then(|attrs| attrs.get(attr)).is_some();
+    match tag {
+        Tag::Img | Tag::Video | Tag::Iframe if !has("src") => report(ViolationKind::MissingAttr(tag.clone(), "src")),
+        Tag::A if !has("href") => report(ViolationKind::MissingAttr(tag.clone(), "href")),
+        _ => {}
+    }
+    let children = element.children.as_deref().unwrap_or_default();
+    if tag.is_void() && !children.is_empty() {
+        report(ViolationKind::UnexpectedChildren(tag.clone()));
+    }
+    match tag {
+        Tag::Li if !matches!(parent, Some(Tag::Ol | Tag::Ul)) => report(ViolationKind::Misplaced(Tag::Li, &[Tag::Ol, Tag::Ul])),
+        Tag::Figcaption if parent != Some(&Tag::Figure) => report(ViolationKind::Misplaced(Tag::Figcaption, &[Tag::Figure])),
+        _ => {}
+    }
+    if in_paragraph && tag.is_block() {
+        report(ViolationKind::BlockInParagraph(tag.clone()));
+    }
+    let in_paragraph = *tag == Tag::P || (in_paragraph && !tag.is_block());
+    validate_nodes(children, Some(tag), in_paragraph, path, violations);
+}
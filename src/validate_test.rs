@@ -0,0 +1,88 @@
+#[cfg(test)]
+mod test_validate {
+    use crate::entity::*;
+    use crate::validate::*;
+
+    fn parse(s: &str) -> Content {
+        lenient(|| serde_json::from_str(s)).unwrap()
+    }
+
+    #[test]
+    fn test_normal() {
+        let content = parse(r#"[{"tag":"h3","children":["Title"]},{"tag":"p","children":["Hello, ",{"tag":"a","attrs":{"href":"https://telegra.ph/"},"children":["world"]},{"tag":"br"}]},{"tag":"ul","children":[{"tag":"li","children":["item"]}]},{"tag":"figure","children":[{"tag":"img","attrs":{"src":"/file/1.jpg"}},{"tag":"figcaption","children":["caption"]}]}]"#);
+        assert_eq!(content.validate(), Ok(()));
+        assert!(content.check().is_ok());
+    }
+
+    #[test]
+    fn test_violations() {
+        let content = parse(r#"[{"tag":"img"},{"tag":"p","children":[{"tag":"a","children":["link"]},{"tag":"br","children":["x"]},{"tag":"ul"}]},{"tag":"li"},{"tag":"figcaption"},{"tag":"div","attrs":{"class":"x"}}]"#);
+        let violations = content.validate().err().unwrap();
+        let expected = vec![
+            Violation { path: vec![0], kind: ViolationKind::MissingAttr(Tag::Img, "src") },
+            Violation { path: vec![1, 0], kind: ViolationKind::MissingAttr(Tag::A, "href") },
+            Violation { path: vec![1, 1], kind: ViolationKind::UnexpectedChildren(Tag::Br) },
+            Violation { path: vec![1, 2], kind: ViolationKind::BlockInParagraph(Tag::Ul) },
+            Violation { path: vec![2], kind: ViolationKind::Misplaced(Tag::Li, &[Tag::Ol, Tag::Ul]) },
+            Violation { path: vec![3], kind: ViolationKind::Misplaced(Tag::Figcaption, &[Tag::Figure]) },
+            Violation { path: vec![4], kind: ViolationKind::UnknownTag("div".into()) },
+            Violation { path: vec![4], kind: ViolationKind::UnknownAttr("class".into()) },
+        ];
+        assert_eq!(violations, expected);
+        assert_eq!(violations[4].to_string(), "node 2: li outside of ol or ul");
+        assert_eq!(violations[1].to_string(), "node 1.0: a without href");
+    }
+
+    #[test]
+    fn test_block_below_paragraph() {
+        let content = parse(r#"[{"tag":"p","children":[{"tag":"a","attrs":{"href":"https://x/"},"children":[{"tag":"figure","children":[{"tag":"img","attrs":{"src":"/i.jpg"}}]}]},{"tag":"em","children":[{"tag":"b","children":[{"tag":"h4","children":["x"]}]}]}]},{"tag":"blockquote","children":[{"tag":"figure","children":[{"tag":"img","attrs":{"src":"/i.jpg"}}]}]}]"#);
+        let violations = content.validate().err().unwrap();
+        let expected = vec![
+            Violation { path: vec![0, 0, 0], kind: ViolationKind::BlockInParagraph(Tag::Figure) },
+            Violation { path: vec![0, 1, 0, 0], kind: ViolationKind::BlockInParagraph(Tag::H4) },
+        ];
+        assert_eq!(violations, expected);
+        assert_eq!(violations[0].to_string(), "node 0.0.0: figure inside p");
+        // only the outermost misplaced block is reported
+        let content = parse(r#"[{"tag":"p","children":[{"tag":"blockquote","children":[{"tag":"p","children":["x"]}]}]}]"#);
+        let violations = content.validate().err().unwrap();
+        assert_eq!(violations, vec![Violation { path: vec![0, 0], kind: ViolationKind::BlockInParagraph(Tag::Blockquote) }]);
+    }
+
+    #[test]
+    fn test_check() {
+        let content = parse(r#"[{"tag":"video"},{"tag":"hr","children":[{"tag":"b"}]}]"#);
+        let e = content.check().err().unwrap();
+        assert_eq!(e.field, "content");
+        assert_eq!(e.actual, "node 0: video without src; node 1: hr with children");
+    }
+}
+
+#[cfg(test)]
+mod test_page_methods {
+    use crate::entity::*;
+    use crate::methods::*;
+
+    #[test]
+    fn test_try_new() {
+        let good: Content = serde_json::from_str(r#"[{"tag":"p","children":["Hello, world!"]}]"#).unwrap();
+        assert!(CreatePage::try_new("1234567".into(), "title".into(), good.clone(), false).is_ok());
+        assert!(EditPage::try_new("1234567".into(), "path".into(), "title".into(), good.clone(), false).is_ok());
+        let e = CreatePage::try_new("1234567".into(), "".into(), good, false).err().unwrap();
+        assert_eq!(e.field, "title");
+        let bad: Content = serde_json::from_str(r#"[{"tag":"iframe"}]"#).unwrap();
+        let e = EditPage::try_new("1234567".into(), "path".into(), "title".into(), bad, false).err().unwrap();
+        assert_eq!(e.to_string(), "content: expected well-formed node tree, got node 0: iframe without src");
+    }
+
+    #[test]
+    fn test_new_unchecked() {
+        let bad: Content = serde_json::from_str(r#"[{"tag":"a","children":["link"]}]"#).unwrap();
+        let page = CreatePage::new("1234567".into(), "title".into(), bad.clone(), false);
+        assert_eq!(page.content, bad);
+        let page = EditPage::new("1234567".into(), "path".into(), "title".into(), bad.clone(), false);
+        assert_eq!(page.content, bad);
+        let e = CreatePage::try_new("1234567".into(), "title".into(), bad, false).err().unwrap();
+        assert_eq!(e.to_string(), "content: expected well-formed node tree, got node 0: a without href");
+    }
+}